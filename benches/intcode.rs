@@ -0,0 +1,62 @@
+use advent19::{parse_intcode, Program};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 20;
+const FORKS: u32 = 10_000;
+
+fn bench(code: &[i64], input: &[i64], cached: bool) -> Duration {
+  let start = Instant::now();
+  for _ in 0..ITERATIONS {
+    let mut program = Program::new(code.to_vec());
+    program.set_decode_cache(cached);
+    program.extend_input(input.iter().copied());
+    black_box(program.run_to_halt().unwrap());
+  }
+  start.elapsed() / ITERATIONS
+}
+
+// Time to get a fresh machine by building it from the code or by cloning a prototype.
+fn fork(code: &[i64]) -> (Duration, Duration) {
+  let start = Instant::now();
+  for _ in 0..FORKS {
+    black_box(Program::new(code.to_vec()));
+  }
+  let built = start.elapsed() / FORKS;
+
+  let prototype = Program::new(code.to_vec());
+  let start = Instant::now();
+  for _ in 0..FORKS {
+    black_box(prototype.clone());
+  }
+  (built, start.elapsed() / FORKS)
+}
+
+fn main() {
+  let programs = [
+    ("day5", include_str!("../inputs/day5.txt"), &[5][..]),
+    ("day9", include_str!("../inputs/day9.txt"), &[2][..]),
+    ("day13", include_str!("../inputs/day13.txt"), &[][..]),
+  ];
+
+  for (name, source, input) in programs.iter() {
+    let code = parse_intcode(source).unwrap();
+    let uncached = bench(&code, input, false);
+    let cached = bench(&code, input, true);
+    println!(
+      "{:<6} uncached {:>10.3?}  cached {:>10.3?}  speedup {:.2}x",
+      name,
+      uncached,
+      cached,
+      uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+    let (built, cloned) = fork(&code);
+    println!(
+      "{:<6} new      {:>10.3?}  clone  {:>10.3?}  speedup {:.2}x",
+      name,
+      built,
+      cloned,
+      built.as_secs_f64() / cloned.as_secs_f64()
+    );
+  }
+}
@@ -0,0 +1,136 @@
+use crate::computer::{IntcodeError, Program, State};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum AsciiEvent {
+  Line(String),
+  Value(i64),
+  NeedsInput { prompt: String },
+  Halted,
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct NonAscii {
+  pub character: char,
+}
+
+impl fmt::Display for NonAscii {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "cannot send non-ASCII character {:?}", self.character)
+  }
+}
+
+impl std::error::Error for NonAscii {}
+
+pub struct AsciiProgram {
+  program: Program,
+  line: String,
+}
+
+impl AsciiProgram {
+  pub fn new(program: Program) -> Self {
+    Self {
+      program,
+      line: String::new(),
+    }
+  }
+
+  pub fn program(&self) -> &Program {
+    &self.program
+  }
+
+  pub fn program_mut(&mut self) -> &mut Program {
+    &mut self.program
+  }
+
+  pub fn into_program(self) -> Program {
+    self.program
+  }
+
+  /// Nothing is queued when the line has a character outside ASCII
+  pub fn send_line(&mut self, line: &str) -> Result<(), NonAscii> {
+    if let Some(character) = line.chars().find(|c| !c.is_ascii()) {
+      return Err(NonAscii { character });
+    }
+    self
+      .program
+      .extend_input(line.bytes().chain(Some(b'\n')).map(i64::from));
+    Ok(())
+  }
+
+  pub fn run(&mut self) -> Result<AsciiEvent, IntcodeError> {
+    loop {
+      match self.program.run()? {
+        State::Output(10) => return Ok(AsciiEvent::Line(std::mem::take(&mut self.line))),
+        State::Output(value @ 0..=127) => self.line.push(value as u8 as char),
+        State::Output(value) => return Ok(AsciiEvent::Value(value)),
+        State::NeedsInput => {
+          return Ok(AsciiEvent::NeedsInput {
+            prompt: std::mem::take(&mut self.line),
+          })
+        }
+        State::Halted if !self.line.is_empty() => {
+          return Ok(AsciiEvent::Line(std::mem::take(&mut self.line)))
+        }
+        State::Halted => return Ok(AsciiEvent::Halted),
+        state => return Err(self.program.blocked(state)),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asm::assemble;
+
+  #[test]
+  fn lines_and_values() {
+    let code = assemble(
+      "
+            out #72
+            out #105
+            out #10
+            out #1000
+      loop: in c
+            out c
+            eq c, #10, t
+            jf t, #loop
+            out #62
+            hlt
+      c:    .data 0
+      t:    .data 0
+      ",
+    )
+    .unwrap();
+    let mut ascii = AsciiProgram::new(Program::new(code));
+    assert_eq!(ascii.run(), Ok(AsciiEvent::Line("Hi".to_owned())));
+    assert_eq!(ascii.run(), Ok(AsciiEvent::Value(1000)));
+    assert_eq!(
+      ascii.run(),
+      Ok(AsciiEvent::NeedsInput {
+        prompt: String::new()
+      })
+    );
+    assert_eq!(ascii.send_line("é"), Err(NonAscii { character: 'é' }));
+    assert!(ascii.program().input().is_empty());
+    ascii.send_line("ok").unwrap();
+    assert_eq!(ascii.run(), Ok(AsciiEvent::Line("ok".to_owned())));
+    assert_eq!(ascii.run(), Ok(AsciiEvent::Line(">".to_owned())));
+    assert_eq!(ascii.run(), Ok(AsciiEvent::Halted));
+  }
+
+  #[test]
+  fn prompt_without_newline() {
+    // out #63; in 6; hlt
+    let mut ascii = AsciiProgram::new(Program::new(vec![104, 63, 3, 6, 99, 0, 0]));
+    assert_eq!(
+      ascii.run(),
+      Ok(AsciiEvent::NeedsInput {
+        prompt: "?".to_owned()
+      })
+    );
+    ascii.send_line("y").unwrap();
+    assert_eq!(ascii.run(), Ok(AsciiEvent::Halted));
+  }
+}
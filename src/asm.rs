@@ -0,0 +1,296 @@
+use crate::computer::{Opcode, ParamMode};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+const OPCODES: &[i64] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct AsmError {
+  pub line: usize,
+  pub kind: AsmErrorKind,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum AsmErrorKind {
+  UnknownMnemonic(String),
+  UnknownDirective(String),
+  OperandCount { expected: usize, found: usize },
+  InvalidOperand(String),
+  ImmediateWrite,
+  UndefinedLabel(String),
+  DuplicateLabel(String),
+  AddressMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for AsmError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}: ", self.line)?;
+    match &self.kind {
+      AsmErrorKind::UnknownMnemonic(m) => write!(f, "unknown mnemonic `{}`", m),
+      AsmErrorKind::UnknownDirective(d) => write!(f, "unknown directive `{}`", d),
+      AsmErrorKind::OperandCount { expected, found } => {
+        write!(f, "expected {} operands, found {}", expected, found)
+      }
+      AsmErrorKind::InvalidOperand(o) => write!(f, "invalid operand `{}`", o),
+      AsmErrorKind::ImmediateWrite => write!(f, "immediate operand used as write target"),
+      AsmErrorKind::UndefinedLabel(l) => write!(f, "undefined label `{}`", l),
+      AsmErrorKind::DuplicateLabel(l) => write!(f, "label `{}` defined twice", l),
+      AsmErrorKind::AddressMismatch { expected, actual } => {
+        write!(
+          f,
+          "expected address {}, but assembling at {}",
+          expected, actual
+        )
+      }
+    }
+  }
+}
+
+impl std::error::Error for AsmError {}
+
+enum Value<'a> {
+  Number(i64),
+  Label(&'a str),
+}
+
+struct Operand<'a> {
+  mode: ParamMode,
+  value: Value<'a>,
+}
+
+enum Statement<'a> {
+  Instruction(i64, Opcode, Vec<Operand<'a>>),
+  Data(Vec<Value<'a>>),
+}
+
+fn is_label(s: &str) -> bool {
+  let mut chars = s.chars();
+  chars
+    .next()
+    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_value(s: &str) -> Option<Value<'_>> {
+  if is_label(s) {
+    Some(Value::Label(s))
+  } else {
+    s.parse().ok().map(Value::Number)
+  }
+}
+
+fn parse_operand(s: &str) -> Option<Operand<'_>> {
+  let (mode, value) = if let Some(value) = s.strip_prefix('#') {
+    (ParamMode::Immediate, value)
+  } else if let Some(value) = s.strip_prefix('@') {
+    (ParamMode::Relative, value)
+  } else {
+    (ParamMode::Position, s)
+  };
+  Some(Operand {
+    mode,
+    value: parse_value(value.trim())?,
+  })
+}
+
+fn lookup(mnemonic: &str) -> Option<(i64, Opcode)> {
+  OPCODES
+    .iter()
+    .filter_map(|&code| Some((code, Opcode::try_from(code).ok()?)))
+    .find(|(_, opcode)| opcode.mnemonic() == mnemonic)
+}
+
+fn parse_statement(line: &str) -> Result<Statement<'_>, AsmErrorKind> {
+  let (head, rest) = match line.find(char::is_whitespace) {
+    Some(idx) => (&line[..idx], line[idx..].trim()),
+    None => (line, ""),
+  };
+  let args: Vec<&str> = if rest.is_empty() {
+    Vec::new()
+  } else {
+    rest.split(',').map(str::trim).collect()
+  };
+
+  if let Some(directive) = head.strip_prefix('.') {
+    if directive != "data" {
+      return Err(AsmErrorKind::UnknownDirective(head.to_owned()));
+    }
+    let values = args
+      .iter()
+      .map(|&arg| parse_value(arg).ok_or_else(|| AsmErrorKind::InvalidOperand(arg.to_owned())))
+      .collect::<Result<_, _>>()?;
+    return Ok(Statement::Data(values));
+  }
+
+  let (base, opcode) =
+    lookup(head).ok_or_else(|| AsmErrorKind::UnknownMnemonic(head.to_owned()))?;
+  let arity = opcode.modes().len();
+  if args.len() != arity {
+    return Err(AsmErrorKind::OperandCount {
+      expected: arity,
+      found: args.len(),
+    });
+  }
+  let operands: Vec<_> = args
+    .iter()
+    .map(|&arg| parse_operand(arg).ok_or_else(|| AsmErrorKind::InvalidOperand(arg.to_owned())))
+    .collect::<Result<_, _>>()?;
+  if opcode.writes() && operands.last().map(|o| o.mode) == Some(ParamMode::Immediate) {
+    return Err(AsmErrorKind::ImmediateWrite);
+  }
+  Ok(Statement::Instruction(base, opcode, operands))
+}
+
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+  let mut labels = HashMap::new();
+  let mut statements = Vec::new();
+  let mut address = 0;
+
+  for (idx, line) in source.lines().enumerate() {
+    let err = |kind| AsmError {
+      line: idx + 1,
+      kind,
+    };
+    let mut line = line.split(';').next().unwrap_or_default().trim();
+
+    while let Some(colon) = line.find(':') {
+      let name = line[..colon].trim();
+      if is_label(name) {
+        if labels.insert(name, address).is_some() {
+          return Err(err(AsmErrorKind::DuplicateLabel(name.to_owned())));
+        }
+      } else if let Ok(expected) = name.parse::<usize>() {
+        if expected != address {
+          return Err(err(AsmErrorKind::AddressMismatch {
+            expected,
+            actual: address,
+          }));
+        }
+      } else {
+        break;
+      }
+      line = line[colon + 1..].trim();
+    }
+
+    if line.is_empty() {
+      continue;
+    }
+    let statement = parse_statement(line).map_err(err)?;
+    address += match &statement {
+      Statement::Instruction(_, opcode, _) => opcode.size(),
+      Statement::Data(values) => values.len(),
+    };
+    statements.push((idx + 1, statement));
+  }
+
+  let mut code = Vec::with_capacity(address);
+  for (line, statement) in statements {
+    let resolve = |value: &Value| match *value {
+      Value::Number(n) => Ok(n),
+      Value::Label(label) => labels
+        .get(label)
+        .map(|&a| a as i64)
+        .ok_or_else(|| AsmError {
+          line,
+          kind: AsmErrorKind::UndefinedLabel(label.to_owned()),
+        }),
+    };
+    match statement {
+      Statement::Instruction(base, _, operands) => {
+        let modes = operands
+          .iter()
+          .enumerate()
+          .map(|(i, operand)| operand.mode.digit() * 10i64.pow(i as u32 + 2))
+          .sum::<i64>();
+        code.push(base + modes);
+        for operand in &operands {
+          code.push(resolve(&operand.value)?);
+        }
+      }
+      Statement::Data(values) => {
+        for value in &values {
+          code.push(resolve(value)?);
+        }
+      }
+    }
+  }
+  Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::disasm::disassemble;
+
+  #[test]
+  fn labels() {
+    let source = "
+      ; count to 16, printing the cells after the relative base
+      start: arb #1
+      out @-1
+      add counter, #1, counter
+      eq counter, #16, done
+      jf done, #start
+      hlt
+
+      counter: .data 0
+      done:    .data 0
+    ";
+    assert_eq!(
+      assemble(source).unwrap(),
+      vec![109, 1, 204, -1, 1001, 16, 1, 16, 1008, 16, 16, 17, 1006, 17, 0, 99, 0, 0]
+    );
+  }
+
+  #[test]
+  fn round_trip() {
+    let code = vec![
+      3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+      1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105,
+      1, 46, 98, 99,
+    ];
+    let listing: Vec<String> = disassemble(&code).iter().map(|i| i.to_string()).collect();
+    assert_eq!(assemble(&listing.join("\n")).unwrap(), code);
+
+    // hlt with a mode digit and out with one digit too many only survive as data
+    let code = vec![1099, 10104, 7, 99];
+    let listing: Vec<String> = disassemble(&code).iter().map(|i| i.to_string()).collect();
+    assert_eq!(listing, vec!["    0: .data 1099, 10104, 7", "    3: hlt"]);
+    assert_eq!(assemble(&listing.join("\n")).unwrap(), code);
+  }
+
+  #[test]
+  fn errors() {
+    let err = |source| assemble(source).unwrap_err();
+    assert_eq!(
+      err("hlt\nfoo 1"),
+      AsmError {
+        line: 2,
+        kind: AsmErrorKind::UnknownMnemonic("foo".to_owned())
+      }
+    );
+    assert_eq!(
+      err("add 1, 2"),
+      AsmError {
+        line: 1,
+        kind: AsmErrorKind::OperandCount {
+          expected: 3,
+          found: 2
+        }
+      }
+    );
+    assert_eq!(err("in #3").kind, AsmErrorKind::ImmediateWrite);
+    assert_eq!(
+      err("jt #1, nowhere").kind,
+      AsmErrorKind::UndefinedLabel("nowhere".to_owned())
+    );
+    assert_eq!(
+      err("hlt\n0: hlt").kind,
+      AsmErrorKind::AddressMismatch {
+        expected: 0,
+        actual: 1
+      }
+    );
+  }
+}
@@ -1,24 +1,17 @@
-const MODULES_MASS: &[u32] = &[
-  106_404, 140_515, 142_745, 120_767, 79665, 54235, 127_391, 72207, 70799, 79485, 103_994, 129_583,
-  132_791, 95135, 121_194, 129_425, 64861, 123_233, 132_805, 87916, 111_395, 126_625, 113_045,
-  61704, 65413, 145_820, 75988, 74717, 115_137, 85331, 86833, 86063, 85464, 139_738, 103_372,
-  101_942, 52741, 77660, 112_745, 103_109, 106_301, 141_714, 74546, 55474, 106_747, 140_234, 60426,
-  145_867, 144_810, 94179, 101_606, 77763, 139_291, 104_246, 148_513, 126_828, 64624, 139_058,
-  85839, 86636, 62198, 137_358, 76711, 87848, 141_711, 114_079, 71639, 95896, 104_522, 61929,
-  72199, 142_790, 137_736, 123_437, 91872, 127_661, 111_179, 51548, 83452, 91196, 117_798, 84484,
-  75517, 83820, 97407, 89181, 71428, 72758, 73076, 109_957, 50601, 74571, 65556, 129_765, 80626,
-  126_995, 73480, 71360, 103_288, 85670,
-];
-
-pub fn main() {
-  let total_fuel: u32 = MODULES_MASS.iter().copied().map(mass_required_fuel).sum();
+use advent19::{parse_lines, read_input};
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let modules_mass: Vec<u32> = parse_lines(&read_input()?)?;
+
+  let total_fuel: u32 = modules_mass.iter().copied().map(mass_required_fuel).sum();
   println!("the total required fuel for all modules is: {}", total_fuel);
 
-  let total_fuel: u32 = MODULES_MASS.iter().copied().map(required_fuel).sum();
+  let total_fuel: u32 = modules_mass.iter().copied().map(required_fuel).sum();
   println!(
     "the total required fuel for all modules including fuel is: {}",
     total_fuel
   );
+  Ok(())
 }
 
 fn calculate_fuel(mass: u32) -> Option<u32> {
@@ -1,75 +1,59 @@
-use advent19::Program;
-use std::iter::empty;
-
-#[rustfmt::skip]
-const GRAVITY_ASSIST_PROGRAM: &[i64] = &[
-  1, 0, 0, 3,
-  1, 1, 2, 3,
-  1, 3, 4, 3,
-  1, 5, 0, 3,
-  2, 1, 10, 19,
-  1, 6, 19, 23,
-  1, 10, 23, 27,
-  2, 27, 13, 31,
-  1, 31, 6, 35,
-  2, 6, 35, 39,
-  1, 39, 5, 43,
-  1, 6, 43, 47,
-  2, 6, 47, 51,
-  1, 51, 5, 55,
-  2, 55, 9, 59,
-  1, 6, 59, 63,
-  1, 9, 63, 67,
-  1, 67, 10, 71,
-  2, 9, 71, 75,
-  1, 6, 75, 79,
-  1, 5, 79, 83,
-  2, 83, 10, 87,
-  1, 87, 5, 91,
-  1, 91, 9, 95,
-  1, 6, 95, 99,
-  2, 99, 10, 103,
-  1, 103, 5, 107,
-  2, 107, 6, 111,
-  1, 111, 5, 115,
-  1, 9, 115, 119,
-  2, 119, 10, 123,
-  1, 6, 123, 127,
-  2, 13, 127, 131,
-  1, 131, 6, 135,
-  1, 135, 10, 139,
-  1, 13, 139, 143,
-  1, 143, 13, 147,
-  1, 5, 147, 151,
-  1, 151, 2, 155,
-  1, 155, 5, 0,
-  99, 2, 0, 14,
-];
+use advent19::{parse_intcode, read_input, Program, Symbolic, SymbolicError};
+use std::ops::RangeInclusive;
 
 const DESIRED_OUTPUT: i64 = 19_690_720;
+const NOUN: usize = 1;
+const VERB: usize = 2;
+const RANGE: RangeInclusive<i64> = 0..=99;
+
+fn run_patched(code: &[i64], noun: i64, verb: i64) -> i64 {
+  let mut program = code.to_vec();
+  program[NOUN] = noun;
+  program[VERB] = verb;
+  let mut prog = Program::new(program);
+  prog.run_to_halt().unwrap();
+  prog.memory().get(0)
+}
 
-pub fn main() {
-  for noun in 0..99 {
-    for verb in 0..99 {
-      let mut program: Vec<_> = GRAVITY_ASSIST_PROGRAM.into();
-      program[1] = noun;
-      program[2] = verb;
-      let mut prog = Program::new(program);
-      prog.run(empty());
-      let result = prog.code()[0];
-      if noun == 12 && verb == 2 {
-        println!("part1 solution: {}", result);
-      }
-      if result == DESIRED_OUTPUT {
-        println!(
-          "part2 solution: noun:{}, verb:{} ({})",
-          noun,
-          verb,
-          noun * 100 + verb
-        );
-      }
-    }
+fn solve_symbolic(code: &[i64]) -> Result<Option<(i64, i64)>, SymbolicError> {
+  let mut symbolic = Symbolic::new(code, &[NOUN, VERB]);
+  symbolic.run()?;
+  let result = symbolic.cell(0)?;
+  println!("symbolic result: {}", result);
+  Ok(
+    result
+      .solve(DESIRED_OUTPUT, &[(NOUN, RANGE), (VERB, RANGE)])
+      .map(|values| (values[0], values[1])),
+  )
+}
+
+fn brute_force(code: &[i64]) -> Option<(i64, i64)> {
+  RANGE
+    .flat_map(|noun| RANGE.map(move |verb| (noun, verb)))
+    .find(|&(noun, verb)| run_patched(code, noun, verb) == DESIRED_OUTPUT)
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let gravity_assist_program = parse_intcode(&read_input()?)?;
+  println!(
+    "part1 solution: {}",
+    run_patched(&gravity_assist_program, 12, 2)
+  );
+
+  let solution = solve_symbolic(&gravity_assist_program).unwrap_or_else(|error| {
+    println!("symbolic execution failed ({}), brute forcing", error);
+    brute_force(&gravity_assist_program)
+  });
+  match solution {
+    Some((noun, verb)) => println!(
+      "part2 solution: noun:{}, verb:{} ({})",
+      noun,
+      verb,
+      noun * 100 + verb
+    ),
+    None => println!("part2: no solution"),
   }
+  Ok(())
 }
 
 #[cfg(test)]
@@ -77,13 +61,21 @@ mod tests {
   use super::*;
 
   fn run_intcode(program: &[i64]) -> Vec<i64> {
-    let mut prog = Program::new(program);
-    prog.run(empty());
+    let mut prog = Program::new(program.to_vec());
+    prog.run_to_halt().unwrap();
     let mut res = prog.into_code();
     res.truncate(program.len());
     res
   }
 
+  #[test]
+  fn symbolic_matches_brute_force() {
+    let code = parse_intcode(include_str!("../../inputs/day2.txt")).unwrap();
+    let solution = solve_symbolic(&code).unwrap();
+    assert!(solution.is_some());
+    assert_eq!(solution, brute_force(&code));
+  }
+
   #[test]
   fn example1() {
     assert_eq!(run_intcode(&[1, 0, 0, 0, 99i64]), vec![2, 0, 0, 0, 99]);
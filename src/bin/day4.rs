@@ -1,11 +1,13 @@
-fn main() {
-  const START: usize = 265_275;
-  const END: usize = 781_584;
+use advent19::{parse_range, read_input};
 
-  let matching = (START..=END)
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let range = parse_range(&read_input()?)?;
+
+  let matching = range
     .filter(|i| is_valid_password(i.to_string().as_bytes()))
     .count();
   println!("amount of matching passwords: {:?}", matching);
+  Ok(())
 }
 
 fn is_valid_password(password: &[u8]) -> bool {
@@ -27,6 +29,7 @@ fn is_valid_password(password: &[u8]) -> bool {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
   use super::*;
 
@@ -1,53 +1,19 @@
-use advent19::Program;
-use std::iter::{empty, once};
-
-const AIRCO_CODE: &[i64] = &[
-  3, 225, 1, 225, 6, 6, 1100, 1, 238, 225, 104, 0, 1001, 152, 55, 224, 1001, 224, -68, 224, 4, 224,
-  1002, 223, 8, 223, 1001, 224, 4, 224, 1, 224, 223, 223, 1101, 62, 41, 225, 1101, 83, 71, 225,
-  102, 59, 147, 224, 101, -944, 224, 224, 4, 224, 1002, 223, 8, 223, 101, 3, 224, 224, 1, 224, 223,
-  223, 2, 40, 139, 224, 1001, 224, -3905, 224, 4, 224, 1002, 223, 8, 223, 101, 7, 224, 224, 1, 223,
-  224, 223, 1101, 6, 94, 224, 101, -100, 224, 224, 4, 224, 1002, 223, 8, 223, 101, 6, 224, 224, 1,
-  224, 223, 223, 1102, 75, 30, 225, 1102, 70, 44, 224, 101, -3080, 224, 224, 4, 224, 1002, 223, 8,
-  223, 1001, 224, 4, 224, 1, 223, 224, 223, 1101, 55, 20, 225, 1102, 55, 16, 225, 1102, 13, 94,
-  225, 1102, 16, 55, 225, 1102, 13, 13, 225, 1, 109, 143, 224, 101, -88, 224, 224, 4, 224, 1002,
-  223, 8, 223, 1001, 224, 2, 224, 1, 223, 224, 223, 1002, 136, 57, 224, 101, -1140, 224, 224, 4,
-  224, 1002, 223, 8, 223, 101, 6, 224, 224, 1, 223, 224, 223, 101, 76, 35, 224, 1001, 224, -138,
-  224, 4, 224, 1002, 223, 8, 223, 101, 5, 224, 224, 1, 223, 224, 223, 4, 223, 99, 0, 0, 0, 677, 0,
-  0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1105, 0, 99999, 1105, 227, 247, 1105, 1, 99999, 1005, 227, 99999,
-  1005, 0, 256, 1105, 1, 99999, 1106, 227, 99999, 1106, 0, 265, 1105, 1, 99999, 1006, 0, 99999,
-  1006, 227, 274, 1105, 1, 99999, 1105, 1, 280, 1105, 1, 99999, 1, 225, 225, 225, 1101, 294, 0, 0,
-  105, 1, 0, 1105, 1, 99999, 1106, 0, 300, 1105, 1, 99999, 1, 225, 225, 225, 1101, 314, 0, 0, 106,
-  0, 0, 1105, 1, 99999, 1008, 677, 677, 224, 1002, 223, 2, 223, 1006, 224, 329, 1001, 223, 1, 223,
-  8, 677, 226, 224, 102, 2, 223, 223, 1006, 224, 344, 101, 1, 223, 223, 1107, 226, 226, 224, 1002,
-  223, 2, 223, 1006, 224, 359, 1001, 223, 1, 223, 1108, 677, 226, 224, 102, 2, 223, 223, 1005, 224,
-  374, 1001, 223, 1, 223, 1007, 226, 226, 224, 102, 2, 223, 223, 1006, 224, 389, 1001, 223, 1, 223,
-  108, 677, 677, 224, 1002, 223, 2, 223, 1005, 224, 404, 1001, 223, 1, 223, 1007, 677, 677, 224,
-  102, 2, 223, 223, 1005, 224, 419, 1001, 223, 1, 223, 8, 226, 677, 224, 102, 2, 223, 223, 1005,
-  224, 434, 101, 1, 223, 223, 1008, 677, 226, 224, 102, 2, 223, 223, 1006, 224, 449, 1001, 223, 1,
-  223, 7, 677, 677, 224, 102, 2, 223, 223, 1006, 224, 464, 1001, 223, 1, 223, 8, 226, 226, 224,
-  1002, 223, 2, 223, 1005, 224, 479, 1001, 223, 1, 223, 7, 226, 677, 224, 102, 2, 223, 223, 1006,
-  224, 494, 1001, 223, 1, 223, 7, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 509, 101, 1, 223,
-  223, 107, 677, 677, 224, 102, 2, 223, 223, 1006, 224, 524, 101, 1, 223, 223, 1007, 677, 226, 224,
-  102, 2, 223, 223, 1006, 224, 539, 101, 1, 223, 223, 107, 226, 226, 224, 1002, 223, 2, 223, 1006,
-  224, 554, 101, 1, 223, 223, 1008, 226, 226, 224, 102, 2, 223, 223, 1006, 224, 569, 1001, 223, 1,
-  223, 1107, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 584, 101, 1, 223, 223, 1107, 226, 677,
-  224, 102, 2, 223, 223, 1005, 224, 599, 101, 1, 223, 223, 1108, 226, 677, 224, 102, 2, 223, 223,
-  1005, 224, 614, 101, 1, 223, 223, 108, 677, 226, 224, 102, 2, 223, 223, 1005, 224, 629, 101, 1,
-  223, 223, 107, 226, 677, 224, 102, 2, 223, 223, 1006, 224, 644, 1001, 223, 1, 223, 1108, 226,
-  226, 224, 1002, 223, 2, 223, 1006, 224, 659, 101, 1, 223, 223, 108, 226, 226, 224, 102, 2, 223,
-  223, 1005, 224, 674, 101, 1, 223, 223, 4, 223, 99, 226,
-];
+use advent19::{parse_intcode, read_input, Program};
 
 fn run_intcode(intcode: &[i64], input: i64) -> i64 {
-  let mut program = Program::new(intcode);
-  let res = program.run(once(input)).unwrap();
-  assert!(program.run(empty()).is_none());
+  let mut program = Program::new(intcode.to_vec());
+  program.push_input(input);
+  let output = program.run_to_halt().unwrap();
+  let (&res, tests) = output.split_last().unwrap();
+  assert!(tests.iter().all(|&test| test == 0));
   res
 }
 
-fn main() {
-  println!("airco output: {:?}", run_intcode(AIRCO_CODE, 1));
-  println!("diagnostic code: {:?}", run_intcode(AIRCO_CODE, 5));
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let airco_code = parse_intcode(&read_input()?)?;
+  println!("airco output: {:?}", run_intcode(&airco_code, 1));
+  println!("diagnostic code: {:?}", run_intcode(&airco_code, 5));
+  Ok(())
 }
 
 #[cfg(test)]
@@ -57,7 +23,7 @@ mod tests {
   #[test]
   fn example1() {
     let mut program = Program::new(&[1002, 4, 3, 4, 33i64] as &[i64]);
-    program.run(empty());
+    program.run_to_halt().unwrap();
     assert_eq!(&program.into_code()[0..5], &[1002, 4, 3, 4, 99i64]);
   }
 
@@ -114,4 +80,21 @@ mod tests {
     assert_eq!(run_intcode(CODE, 8), 1000);
     assert_eq!(run_intcode(CODE, 10), 1001);
   }
+
+  #[test]
+  fn diagnostic_coverage() {
+    let airco_code = parse_intcode(include_str!("../../inputs/day5.txt")).unwrap();
+    let coverage = |input| {
+      let mut program = Program::new(airco_code.clone());
+      program.set_coverage(true);
+      program.push_input(input);
+      program.run_to_halt().unwrap();
+      program.coverage().unwrap().summary(program.memory())
+    };
+    let (airco, thermal) = (coverage(1), coverage(5));
+    // Only the thermal radiator diagnostic branches
+    assert_eq!(airco.taken, 0);
+    assert!(thermal.taken > 0);
+    assert!(thermal.percent() > airco.percent());
+  }
 }
@@ -1,38 +1,17 @@
-use advent19::{Permuter, Program};
+use advent19::{channel, parse_intcode, read_input, Executor, Permuter, Program};
 use std::iter::once;
 
-const AMPLIFIER_CODE: &[i64] = &[
-  3, 8, 1001, 8, 10, 8, 105, 1, 0, 0, 21, 42, 67, 88, 101, 114, 195, 276, 357, 438, 99999, 3, 9,
-  101, 3, 9, 9, 1002, 9, 4, 9, 1001, 9, 5, 9, 102, 4, 9, 9, 4, 9, 99, 3, 9, 1001, 9, 3, 9, 1002, 9,
-  2, 9, 101, 2, 9, 9, 102, 2, 9, 9, 1001, 9, 5, 9, 4, 9, 99, 3, 9, 102, 4, 9, 9, 1001, 9, 3, 9,
-  102, 4, 9, 9, 101, 4, 9, 9, 4, 9, 99, 3, 9, 101, 2, 9, 9, 1002, 9, 3, 9, 4, 9, 99, 3, 9, 101, 4,
-  9, 9, 1002, 9, 5, 9, 4, 9, 99, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9, 101, 1,
-  9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9,
-  101, 1, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4,
-  9, 99, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9,
-  1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9,
-  4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 99, 3, 9,
-  102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4,
-  9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9, 101, 1,
-  9, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 99, 3, 9, 102, 2, 9, 9, 4, 9, 3,
-  9, 101, 1, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 101, 1, 9, 9,
-  4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1002,
-  9, 2, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 99, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4,
-  9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002,
-  9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3,
-  9, 101, 2, 9, 9, 4, 9, 99,
-];
-
 fn get_max_truster_value(code: &[i64]) -> (i64, [i64; 5]) {
   let permuter = Permuter::new([0, 1, 2, 3, 4]);
+  let amplifier = Program::new(code.to_vec());
 
   once(permuter.combination())
     .chain(permuter)
     .map(|combination| {
       let signal = combination.iter().fold(0, |signal, &phase| {
-        Program::new(code)
-          .run([phase, signal].iter().copied())
-          .unwrap()
+        let mut program = amplifier.clone();
+        program.extend_input(vec![phase, signal]);
+        program.run().unwrap().output().unwrap()
       });
       (signal, combination)
     })
@@ -42,29 +21,36 @@ fn get_max_truster_value(code: &[i64]) -> (i64, [i64; 5]) {
 
 fn max_truster_value_feedback(code: &[i64]) -> (i64, [i64; 5]) {
   let permuter = Permuter::new([5, 6, 7, 8, 9]);
+  let amplifier = Program::new(code.to_vec());
 
   once(permuter.combination())
     .chain(permuter)
     .map(|combination| {
       let mut programs = [
-        Program::new(code),
-        Program::new(code),
-        Program::new(code),
-        Program::new(code),
-        Program::new(code),
+        amplifier.clone(),
+        amplifier.clone(),
+        amplifier.clone(),
+        amplifier.clone(),
+        amplifier.clone(),
       ];
+      let channels: Vec<_> = combination
+        .iter()
+        .map(|&phase| {
+          let (sender, receiver) = channel();
+          sender.send(phase);
+          (sender, receiver)
+        })
+        .collect();
+      channels[0].0.send(0);
 
-      let signal = programs
-        .iter_mut()
-        .zip(combination.iter().copied())
-        .fold(0, |signal, (program, phase)| {
-          program.run([phase, signal].iter().copied()).unwrap()
-        });
-
-      let signal = (0..5)
-        .cycle()
-        .try_fold(signal, |s, i| programs[i].run(once(s)).ok_or(s))
-        .unwrap_err();
+      let mut executor = Executor::new();
+      for (i, program) in programs.iter_mut().enumerate() {
+        let input = &channels[i].1;
+        let output = &channels[(i + 1) % channels.len()].0;
+        executor.spawn(async move { program.run_async(input, output).await.unwrap() });
+      }
+      assert_eq!(executor.run(), 0);
+      let signal = channels[0].1.try_recv().unwrap();
 
       (signal, combination)
     })
@@ -72,11 +58,13 @@ fn max_truster_value_feedback(code: &[i64]) -> (i64, [i64; 5]) {
     .unwrap()
 }
 
-fn main() {
-  let result = get_max_truster_value(&AMPLIFIER_CODE);
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let amplifier_code = parse_intcode(&read_input()?)?;
+  let result = get_max_truster_value(&amplifier_code);
   println!("thruster signal: {:?}", result);
-  let result = max_truster_value_feedback(&AMPLIFIER_CODE);
+  let result = max_truster_value_feedback(&amplifier_code);
   println!("thruster signal + feedback: {:?}", result);
+  Ok(())
 }
 
 #[cfg(test)]
@@ -0,0 +1,10 @@
+use advent19::{disassemble, parse_intcode, read_input};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let code = parse_intcode(&read_input()?)?;
+
+  for item in disassemble(&code) {
+    println!("{}", item);
+  }
+  Ok(())
+}
@@ -0,0 +1,31 @@
+use advent19::{parse_intcode, AsciiEvent, AsciiProgram, Program};
+use std::io::{self, BufRead, Write};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let path = std::env::args()
+    .nth(1)
+    .ok_or("usage: intcode-ascii <program.txt>")?;
+  let code = parse_intcode(&std::fs::read_to_string(path)?)?;
+
+  let mut ascii = AsciiProgram::new(Program::new(code));
+  let stdin = io::stdin();
+  loop {
+    match ascii.run()? {
+      AsciiEvent::Line(line) => println!("{}", line),
+      AsciiEvent::Value(value) => println!("[value: {}]", value),
+      AsciiEvent::NeedsInput { prompt } => {
+        print!("{}", prompt);
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+          break;
+        }
+        if let Err(err) = ascii.send_line(line.trim_end_matches(['\r', '\n'])) {
+          eprintln!("{}", err);
+        }
+      }
+      AsciiEvent::Halted => break,
+    }
+  }
+  Ok(())
+}
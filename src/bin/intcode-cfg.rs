@@ -0,0 +1,26 @@
+use advent19::{control_flow, parse_intcode, read_input, Opcode, Program, Step};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let mut code = parse_intcode(&read_input()?)?;
+  let input = std::env::args()
+    .skip(2)
+    .map(|arg| arg.parse())
+    .collect::<Result<Vec<i64>, _>>()?;
+
+  // Programs that patch their own code from input (like the day 5 diagnostic) only show their
+  // real structure once the patch is in place, so with input the graph is built from memory at
+  // the first jump after all of it has been read.
+  if !input.is_empty() {
+    let mut program = Program::new(code);
+    program.extend_input(input);
+    while let Step::Executed(event) = program.step()? {
+      let jump = matches!(event.opcode, Opcode::JumpIfTrue(_) | Opcode::JumpIfFalse(_));
+      if jump && program.input().is_empty() {
+        break;
+      }
+    }
+    code = program.memory().to_vec();
+  }
+  print!("{}", control_flow(&code).dot());
+  Ok(())
+}
@@ -0,0 +1,20 @@
+use advent19::{parse_intcode, read_input, Program};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let code = parse_intcode(&read_input()?)?;
+  let input = std::env::args()
+    .skip(2)
+    .map(|arg| arg.parse())
+    .collect::<Result<Vec<i64>, _>>()?;
+
+  let mut program = Program::new(code);
+  program.set_coverage(true);
+  program.extend_input(input);
+  let output = program.run_to_halt()?;
+  println!("output: {:?}\n", output);
+
+  if let Some(coverage) = program.coverage() {
+    print!("{}", coverage.report(program.memory()));
+  }
+  Ok(())
+}
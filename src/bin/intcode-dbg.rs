@@ -0,0 +1,351 @@
+use advent19::{decode_memory, parse_intcode, Event, IntcodeError, Program, Step};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+commands:
+  s [n]            step n instructions (default 1)
+  c                continue until a breakpoint, watchpoint, input request or halt
+  sb [n]           step back n instructions (default 1)
+  rt <addr>        run back to the last time ptr was at addr
+  who <addr>       print the instruction that last wrote a memory cell
+  b <addr>         toggle breakpoint
+  w <addr>         toggle watchpoint on a memory cell
+  i <v> [v...]     queue input values
+  p <addr> [n]     print n memory cells (default 1)
+  set <addr> <v>   write a memory cell
+  rb [v]           print or set the relative base
+  d [addr] [n]     disassemble n instructions (default: 5 from ptr)
+  info             print ptr, relative base, breakpoints, watchpoints and input
+  help             print this message
+  q                quit";
+
+// Enough to step back through any recent loop, without growing for the whole session
+const HISTORY_LIMIT: usize = 100_000;
+
+#[derive(PartialEq, Eq, Debug)]
+enum Stop {
+  Stepped,
+  Breakpoint(usize),
+  Watchpoint { address: usize, old: i64, new: i64 },
+  NeedsInput,
+  Halted,
+  Error(IntcodeError),
+}
+
+struct Debugger {
+  program: Program,
+  breakpoints: BTreeSet<usize>,
+  watchpoints: BTreeMap<usize, i64>,
+  outputs: Vec<i64>,
+}
+
+impl Debugger {
+  fn new(mut program: Program) -> Self {
+    program.set_history(true);
+    program.set_history_limit(Some(HISTORY_LIMIT));
+    Self {
+      program,
+      breakpoints: BTreeSet::new(),
+      watchpoints: BTreeMap::new(),
+      outputs: Vec::new(),
+    }
+  }
+
+  fn watch(&mut self, event: &Event) -> Option<Stop> {
+    let (address, new) = event.write?;
+    let watched = self.watchpoints.get_mut(&address)?;
+    let old = std::mem::replace(watched, new);
+    Some(Stop::Watchpoint { address, old, new })
+  }
+
+  fn step(&mut self) -> Stop {
+    match self.program.step() {
+      Ok(Step::Executed(event)) => {
+        if let Some(value) = event.output() {
+          self.outputs.push(value);
+        }
+        self.watch(&event).unwrap_or(Stop::Stepped)
+      }
+      Ok(Step::NeedsInput) => Stop::NeedsInput,
+      Ok(Step::Halted) => Stop::Halted,
+      Err(err) => Stop::Error(err),
+    }
+  }
+
+  fn run(&mut self, steps: Option<usize>) -> Stop {
+    let mut stop = self.step();
+    let mut taken = 1;
+    while stop == Stop::Stepped && steps.is_none_or(|n| taken < n) {
+      if steps.is_none() && self.breakpoints.contains(&self.program.ptr()) {
+        return Stop::Breakpoint(self.program.ptr());
+      }
+      stop = self.step();
+      taken += 1;
+    }
+    stop
+  }
+
+  // Stepping back rewrites memory behind the watchpoints' backs
+  fn rewound(&mut self, lines: &mut Vec<String>, moved: bool) {
+    let memory = self.program.memory();
+    for (&address, value) in self.watchpoints.iter_mut() {
+      *value = memory.get(address);
+    }
+    if !moved {
+      lines.push("reached the start of the history".to_owned());
+    }
+    lines.extend(self.disassemble(self.program.ptr(), 1));
+  }
+
+  fn disassemble(&self, mut address: usize, count: usize) -> Vec<String> {
+    let memory = self.program.memory();
+    (0..count)
+      .map(|_| {
+        let at = address;
+        let line = match decode_memory(memory, at) {
+          Some(item) => {
+            address += item.size();
+            item.to_string()
+          }
+          None => {
+            address += 1;
+            format!("{:>5}: .data {}", at, memory.get(at))
+          }
+        };
+        let marker = if self.breakpoints.contains(&at) {
+          '*'
+        } else {
+          ' '
+        };
+        format!("{}{}", marker, line)
+      })
+      .collect()
+  }
+
+  fn command(&mut self, line: &str) -> Result<Vec<String>, String> {
+    let mut words = line.split_whitespace();
+    let command = match words.next() {
+      Some(command) => command,
+      None => return Ok(Vec::new()),
+    };
+    let args = words
+      .map(|w| {
+        w.parse::<i64>()
+          .map_err(|_| format!("invalid number `{}`", w))
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    let address = |idx: usize| -> Result<usize, String> {
+      match args.get(idx) {
+        Some(&a) if a >= 0 => Ok(a as usize),
+        Some(a) => Err(format!("invalid address {}", a)),
+        None => Err(format!("missing argument, see `help`:\n{}", HELP)),
+      }
+    };
+
+    let mut lines = Vec::new();
+    match command {
+      "s" | "step" | "c" | "continue" => {
+        let steps = match command {
+          "c" | "continue" => None,
+          _ => Some(args.first().map_or(1, |&n| n.max(1) as usize)),
+        };
+        let stop = self.run(steps);
+        lines.extend(self.outputs.drain(..).map(|v| format!("output: {}", v)));
+        match stop {
+          Stop::Stepped => {}
+          Stop::Breakpoint(address) => lines.push(format!("breakpoint at {}", address)),
+          Stop::Watchpoint { address, old, new } => {
+            lines.push(format!("watchpoint [{}]: {} -> {}", address, old, new))
+          }
+          Stop::NeedsInput => lines.push("waiting for input".to_owned()),
+          Stop::Halted => lines.push("halted".to_owned()),
+          Stop::Error(err) => lines.push(format!("error: {}", err)),
+        }
+        lines.extend(self.disassemble(self.program.ptr(), 1));
+      }
+      "sb" | "back" => {
+        let steps = args.first().map_or(1, |&n| n.max(1));
+        let moved = (0..steps).all(|_| self.program.step_back());
+        self.rewound(&mut lines, moved);
+      }
+      "rt" => {
+        let moved = self.program.run_back_to(address(0)?);
+        self.rewound(&mut lines, moved);
+      }
+      "who" => {
+        let address = address(0)?;
+        match self.program.last_write(address) {
+          Some(write) => {
+            lines.push(format!(
+              "[{}] = {}, was {} before step {}:",
+              address, write.value, write.previous, write.step
+            ));
+            lines.extend(self.disassemble(write.ptr, 1));
+          }
+          None => lines.push(format!("[{}] was not written", address)),
+        }
+      }
+      "b" | "break" => {
+        let address = address(0)?;
+        if !self.breakpoints.remove(&address) {
+          self.breakpoints.insert(address);
+        }
+      }
+      "w" | "watch" => {
+        let address = address(0)?;
+        if self.watchpoints.remove(&address).is_none() {
+          let value = self.program.memory().get(address);
+          self.watchpoints.insert(address, value);
+        }
+      }
+      "i" | "input" => self.program.extend_input(args),
+      "p" | "print" => {
+        let start = address(0)?;
+        let count = args.get(1).map_or(1, |&n| n.max(1) as usize);
+        let memory = self.program.memory();
+        lines.extend((start..start + count).map(|a| format!("[{}] = {}", a, memory.get(a))));
+      }
+      "set" => {
+        let address = address(0)?;
+        let value = *args.get(1).ok_or("missing value")?;
+        self
+          .program
+          .set_cell(address, value)
+          .map_err(|err| format!("address {} exceeds memory limit {}", err.address, err.limit))?;
+        if let Some(watched) = self.watchpoints.get_mut(&address) {
+          *watched = value;
+        }
+      }
+      "rb" => match args.first() {
+        Some(&value) => self.program.set_relative_base(value),
+        None => lines.push(format!("relative base: {}", self.program.relative_base())),
+      },
+      "d" | "disasm" => {
+        let start = if args.is_empty() {
+          self.program.ptr()
+        } else {
+          address(0)?
+        };
+        let count = args.get(1).map_or(5, |&n| n.max(1) as usize);
+        lines.extend(self.disassemble(start, count));
+      }
+      "info" => {
+        lines.push(format!(
+          "ptr: {}, relative base: {}",
+          self.program.ptr(),
+          self.program.relative_base()
+        ));
+        lines.push(format!("breakpoints: {:?}", self.breakpoints));
+        lines.push(format!(
+          "watchpoints: {:?}",
+          self.watchpoints.keys().collect::<Vec<_>>()
+        ));
+        lines.push(format!("input: {:?}", self.program.input()));
+      }
+      "h" | "help" => lines.push(HELP.to_owned()),
+      _ => return Err(format!("unknown command `{}`, see `help`", command)),
+    }
+    Ok(lines)
+  }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let path = std::env::args()
+    .nth(1)
+    .ok_or("usage: intcode-dbg <program.txt>")?;
+  let code = parse_intcode(&std::fs::read_to_string(path)?)?;
+
+  let mut debugger = Debugger::new(Program::new(code));
+  for line in debugger.disassemble(0, 1) {
+    println!("{}", line);
+  }
+
+  let stdin = io::stdin();
+  loop {
+    print!("(dbg) ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if stdin.lock().read_line(&mut line)? == 0 {
+      break;
+    }
+    match line.trim() {
+      "q" | "quit" => break,
+      line => match debugger.command(line) {
+        Ok(lines) => lines.iter().for_each(|line| println!("{}", line)),
+        Err(err) => println!("{}", err),
+      },
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn debugger() -> Debugger {
+    // in 9; eq 9, #8, 10; out 10; hlt
+    Debugger::new(Program::new(vec![3, 9, 1008, 9, 8, 10, 4, 10, 99, 0, 0]))
+  }
+
+  #[test]
+  fn breakpoint_and_input() {
+    let mut dbg = debugger();
+    dbg.command("b 6").unwrap();
+    assert_eq!(
+      dbg.command("c").unwrap(),
+      vec!["waiting for input", "     0: in 9"]
+    );
+    dbg.command("i 8").unwrap();
+    assert_eq!(
+      dbg.command("c").unwrap(),
+      vec!["breakpoint at 6", "*    6: out 10"]
+    );
+    assert_eq!(
+      dbg.command("c").unwrap(),
+      vec!["output: 1", "halted", "     8: hlt"]
+    );
+  }
+
+  #[test]
+  fn watchpoint_and_memory() {
+    let mut dbg = debugger();
+    dbg.command("w 10").unwrap();
+    dbg.command("set 9 3").unwrap();
+    dbg.command("i 7").unwrap();
+    assert_eq!(
+      dbg.command("c").unwrap(),
+      vec!["watchpoint [10]: 0 -> 0", "     6: out 10"]
+    );
+    assert_eq!(dbg.command("p 9 2").unwrap(), vec!["[9] = 7", "[10] = 0"]);
+    dbg.command("rb 5").unwrap();
+    assert_eq!(dbg.command("rb").unwrap(), vec!["relative base: 5"]);
+    assert!(dbg.command("bogus").is_err());
+  }
+
+  #[test]
+  fn reverse_execution() {
+    let mut dbg = debugger();
+    dbg.command("i 8").unwrap();
+    dbg.command("w 10").unwrap();
+    dbg.command("c").unwrap();
+    assert_eq!(
+      dbg.command("who 10").unwrap(),
+      vec!["[10] = 1, was 0 before step 1:", "     2: eq 9, #8, 10"]
+    );
+    assert_eq!(dbg.command("sb").unwrap(), vec!["     2: eq 9, #8, 10"]);
+    assert_eq!(dbg.command("p 10").unwrap(), vec!["[10] = 0"]);
+    assert_eq!(
+      dbg.command("rt 2").unwrap(),
+      vec!["reached the start of the history", "     0: in 9"]
+    );
+    assert_eq!(dbg.command("who 10").unwrap(), vec!["[10] was not written"]);
+
+    // The input is consumed again and the watchpoint fires again
+    assert_eq!(
+      dbg.command("c").unwrap(),
+      vec!["watchpoint [10]: 0 -> 1", "     6: out 10"]
+    );
+  }
+}
@@ -0,0 +1,22 @@
+use advent19::{parse_intcode, read_input, Program};
+
+const TOP: usize = 20;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let code = parse_intcode(&read_input()?)?;
+  let input = std::env::args()
+    .skip(2)
+    .map(|arg| arg.parse())
+    .collect::<Result<Vec<i64>, _>>()?;
+
+  let mut program = Program::new(code);
+  program.set_profiling(true);
+  program.extend_input(input);
+  let output = program.run_to_halt()?;
+  println!("output: {:?}\n", output);
+
+  if let Some(profile) = program.profile() {
+    print!("{}", profile.report(program.memory(), TOP));
+  }
+  Ok(())
+}
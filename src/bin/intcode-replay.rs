@@ -0,0 +1,25 @@
+use advent19::{parse_intcode, read_input, replay, Program, Recording};
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let code = parse_intcode(&read_input()?)?;
+  let path = std::env::args()
+    .nth(2)
+    .ok_or("usage: intcode-replay <program> <replay>")?;
+  let recording = Recording::load(BufReader::new(File::open(path)?))?;
+
+  match replay(Program::new(code), &recording)? {
+    None => println!(
+      "replay matches: {} entries, {} instructions",
+      recording.entries().len(),
+      recording.instructions()
+    ),
+    Some(divergence) => {
+      println!("replay diverges at {}", divergence);
+      process::exit(1);
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,8 @@
+use advent19::{parse_intcode, read_input, transpile};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let code = parse_intcode(&read_input()?)?;
+
+  print!("{}", transpile(&code));
+  Ok(())
+}
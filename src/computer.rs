@@ -1,105 +1,821 @@
-pub struct Program {
-  code: Vec<i64>,
+use crate::coverage::Coverage;
+use crate::history::History;
+use crate::memory::{Image, Memory, MemoryLimit};
+use crate::profile::Profile;
+use crate::replay::Recording;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+type Operation = (
+  fn(i64, i64) -> Option<i64>,
+  fn(i64, i64) -> i64,
+  fn(i64, i64) -> i64,
+);
+
+const ADD: Operation = (i64::checked_add, i64::wrapping_add, i64::saturating_add);
+const MUL: Operation = (i64::checked_mul, i64::wrapping_mul, i64::saturating_mul);
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Arithmetic {
+  Wrapping,
+  Checked,
+  Saturating,
+}
+
+#[derive(Clone)]
+struct Checkpoint {
   ptr: usize,
   relative_base: i64,
+  input: VecDeque<i64>,
+  memory: Memory,
 }
 
-impl Program {
-  pub fn new(code: impl Into<Vec<i64>>) -> Self {
-    const MIN_MEMORY: usize = 4096;
+#[derive(Default, Clone)]
+struct LoopDetector {
+  checkpoint: Option<Checkpoint>,
+  steps: u64,
+  period: u64,
+}
 
-    let mut mem: Vec<_> = code.into();
-    mem.extend(std::iter::repeat(0).take(MIN_MEMORY.saturating_sub(mem.len())));
+#[derive(Clone)]
+pub struct Program {
+  pub(crate) memory: Memory,
+  pub(crate) ptr: usize,
+  pub(crate) relative_base: i64,
+  pub(crate) input: VecDeque<i64>,
+  pub(crate) output: Vec<i64>,
+  cache: Option<Rc<Vec<Option<Opcode>>>>,
+  profile: Option<Profile>,
+  coverage: Option<Coverage>,
+  recording: Option<Recording>,
+  pub(crate) history: Option<History>,
+  arithmetic: Arithmetic,
+  budget: Option<u64>,
+  loops: Option<LoopDetector>,
+}
+
+impl Program {
+  pub fn new(code: impl Into<Image>) -> Self {
     Self {
-      code: mem,
+      memory: Memory::from_image(code.into()),
       ptr: 0,
       relative_base: 0,
+      input: VecDeque::new(),
+      output: Vec::new(),
+      cache: Some(Rc::default()),
+      profile: None,
+      coverage: None,
+      recording: None,
+      history: None,
+      arithmetic: Arithmetic::Wrapping,
+      budget: None,
+      loops: None,
     }
   }
 
-  fn offset_value(&self, mode: ParamMode, offset_value: i64) -> Option<usize> {
+  pub fn set_decode_cache(&mut self, enabled: bool) {
+    self.cache = if enabled { Some(Rc::default()) } else { None };
+  }
+
+  pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+    self.arithmetic = arithmetic;
+  }
+
+  pub fn set_budget(&mut self, budget: Option<u64>) {
+    self.budget = budget;
+  }
+
+  pub fn budget(&self) -> Option<u64> {
+    self.budget
+  }
+
+  pub fn set_loop_detection(&mut self, enabled: bool) {
+    self.loops = if enabled {
+      Some(LoopDetector::default())
+    } else {
+      None
+    };
+  }
+
+  pub fn set_profiling(&mut self, enabled: bool) {
+    self.profile = if enabled {
+      Some(Profile::default())
+    } else {
+      None
+    };
+  }
+
+  pub fn profile(&self) -> Option<&Profile> {
+    self.profile.as_ref()
+  }
+
+  pub fn set_coverage(&mut self, enabled: bool) {
+    self.coverage = if enabled {
+      Some(Coverage::default())
+    } else {
+      None
+    };
+  }
+
+  pub fn coverage(&self) -> Option<&Coverage> {
+    self.coverage.as_ref()
+  }
+
+  pub fn set_recording(&mut self, enabled: bool) {
+    self.recording = if enabled {
+      Some(Recording::default())
+    } else {
+      None
+    };
+  }
+
+  pub fn recording(&self) -> Option<&Recording> {
+    self.recording.as_ref()
+  }
+
+  pub fn set_history(&mut self, enabled: bool) {
+    self.history = if enabled {
+      Some(History::default())
+    } else {
+      None
+    };
+  }
+
+  /// Keeps only the most recent `limit` changes of an enabled history
+  pub fn set_history_limit(&mut self, limit: Option<usize>) {
+    if let Some(history) = self.history.as_mut() {
+      history.set_limit(limit);
+    }
+  }
+
+  pub fn history(&self) -> Option<&History> {
+    self.history.as_ref()
+  }
+
+  pub fn push_input(&mut self, value: i64) {
+    self.input.push_back(value);
+    self.reset_loops();
+  }
+
+  pub fn extend_input(&mut self, values: impl IntoIterator<Item = i64>) {
+    self.input.extend(values);
+    self.reset_loops();
+  }
+
+  pub fn pop_input(&mut self) -> Option<i64> {
+    self.input.pop_front()
+  }
+
+  pub fn input(&self) -> &VecDeque<i64> {
+    &self.input
+  }
+
+  pub fn output(&self) -> &[i64] {
+    &self.output
+  }
+
+  pub fn drain_output(&mut self) -> std::vec::Drain<'_, i64> {
+    self.output.drain(..)
+  }
+
+  fn address(&self, instruction: i64, operand: usize, address: i64) -> Result<usize, IntcodeError> {
+    if address >= 0 {
+      Ok(address as usize)
+    } else {
+      Err(IntcodeError::InvalidAddress {
+        ptr: self.ptr,
+        instruction,
+        operand,
+        address,
+      })
+    }
+  }
+
+  fn operand(&self, idx: usize) -> i64 {
+    self.memory.get(self.ptr + idx + 1)
+  }
+
+  fn offset_value(
+    &self,
+    instruction: i64,
+    mode: ParamMode,
+    idx: usize,
+    offset_value: i64,
+  ) -> Result<Option<i64>, IntcodeError> {
     match mode {
-      ParamMode::Position => Some(offset_value as usize),
-      ParamMode::Relative => Some((self.relative_base + offset_value) as usize),
-      _ => None,
+      ParamMode::Position => Ok(Some(offset_value)),
+      ParamMode::Relative => match self.relative_base.checked_add(offset_value) {
+        Some(address) => Ok(Some(address)),
+        None => Err(IntcodeError::RelativeOverflow {
+          ptr: self.ptr,
+          instruction,
+          operand: idx,
+        }),
+      },
+      _ => Ok(None),
     }
   }
 
-  fn arg_value(&self, mode: &[ParamMode], idx: usize) -> i64 {
-    let offset_value = self.code[self.ptr + idx + 1];
-    match self.offset_value(mode[idx], offset_value) {
-      Some(offset) => self.code[offset],
-      None => offset_value,
+  fn arg_value(
+    &self,
+    instruction: i64,
+    mode: &[ParamMode],
+    idx: usize,
+  ) -> Result<i64, IntcodeError> {
+    let offset_value = self.operand(idx);
+    match self.offset_value(instruction, mode[idx], idx, offset_value)? {
+      Some(address) => Ok(self.memory.get(self.address(instruction, idx, address)?)),
+      None => Ok(offset_value),
     }
   }
 
-  fn write(&mut self, mode: &[ParamMode], value: i64) {
+  fn write(
+    &mut self,
+    event: &mut Event,
+    mode: &[ParamMode],
+    value: i64,
+  ) -> Result<(), IntcodeError> {
+    let instruction = event.instruction;
     let idx = mode.len() - 1;
-    let offset_value = self.code[self.ptr + idx + 1];
-    let offset = self.offset_value(mode[idx], offset_value).unwrap();
-    self.code[offset] = value;
-    self.ptr += mode.len() + 1;
+    let offset_value = self.operand(idx);
+    let address = match self.offset_value(instruction, mode[idx], idx, offset_value)? {
+      Some(address) => self.address(instruction, idx, address)?,
+      None => {
+        return Err(IntcodeError::ImmediateWrite {
+          ptr: self.ptr,
+          instruction,
+          operand: idx,
+        })
+      }
+    };
+    let overwritten = self.memory.get(address);
+    self
+      .memory
+      .set(address, value)
+      .map_err(|_| IntcodeError::MemoryLimit {
+        ptr: self.ptr,
+        instruction,
+        operand: idx,
+        address,
+      })?;
+    self.invalidate(address);
+    event.write = Some((address, value));
+    event.overwritten = overwritten;
+    Ok(())
+  }
+
+  pub(crate) fn invalidate(&mut self, address: usize) {
+    if let Some(cache) = self.cache.as_mut() {
+      if let Some(Some(_)) = cache.get(address) {
+        Rc::make_mut(cache)[address] = None;
+      }
+    }
+  }
+
+  fn arithmetic(
+    &self,
+    instruction: i64,
+    (checked, wrapping, saturating): Operation,
+    a: i64,
+    b: i64,
+  ) -> Result<i64, IntcodeError> {
+    match self.arithmetic {
+      Arithmetic::Wrapping => Ok(wrapping(a, b)),
+      Arithmetic::Checked => checked(a, b).ok_or(IntcodeError::Overflow {
+        ptr: self.ptr,
+        instruction,
+      }),
+      Arithmetic::Saturating => Ok(saturating(a, b)),
+    }
   }
 
   #[inline]
-  fn jump(&mut self, jump: bool, mode: &[ParamMode]) {
-    self.ptr = if jump {
-      self.arg_value(&mode, mode.len() - 1) as usize
-    } else {
-      self.ptr + mode.len() + 1
-    };
+  fn jump(
+    &mut self,
+    event: &mut Event,
+    jump: bool,
+    mode: &[ParamMode],
+  ) -> Result<(), IntcodeError> {
+    if jump {
+      let idx = mode.len() - 1;
+      let target = self.arg_value(event.instruction, mode, idx)?;
+      event.read(target);
+      self.ptr = self.address(event.instruction, idx, target)?;
+      event.jumped = true;
+    }
+    Ok(())
   }
 
-  pub fn run(&mut self, mut input: impl Iterator<Item = i64>) -> Option<i64> {
-    loop {
-      match Opcode::from(self.code[self.ptr]) {
-        Opcode::Add(mode) => {
-          let value = self.arg_value(&mode, 0) + self.arg_value(&mode, 1);
-          self.write(&mode, value);
+  fn decode(&self, instruction: i64) -> Result<Opcode, IntcodeError> {
+    Opcode::try_from(instruction).map_err(|err| match err {
+      DecodeError::Opcode => IntcodeError::UnknownOpcode {
+        ptr: self.ptr,
+        instruction,
+      },
+      DecodeError::ParamMode { operand, mode } => IntcodeError::UnknownParamMode {
+        ptr: self.ptr,
+        instruction,
+        operand,
+        mode,
+      },
+    })
+  }
+
+  pub fn step(&mut self) -> Result<Step, IntcodeError> {
+    let i = self.memory.get(self.ptr);
+    let relative_base = self.relative_base;
+    let opcode = match self.cache.as_ref().and_then(|cache| cache.get(self.ptr)) {
+      Some(&Some(opcode)) => opcode,
+      _ => {
+        let opcode = self.decode(i)?;
+        if let Some(cache) = self.cache.as_mut() {
+          let cache = Rc::make_mut(cache);
+          if cache.len() <= self.ptr {
+            cache.resize(self.ptr + 1, None);
+          }
+          cache[self.ptr] = Some(opcode);
         }
-        Opcode::Mul(mode) => {
-          let value = self.arg_value(&mode, 0) * self.arg_value(&mode, 1);
-          self.write(&mode, value);
+        opcode
+      }
+    };
+    let mut event = Event {
+      ptr: self.ptr,
+      instruction: i,
+      opcode,
+      reads: [0; 2],
+      read_count: 0,
+      write: None,
+      overwritten: 0,
+      jumped: false,
+    };
+    match opcode {
+      Opcode::Add(mode) => {
+        let (a, b) = (self.arg_value(i, &mode, 0)?, self.arg_value(i, &mode, 1)?);
+        event.read(a);
+        event.read(b);
+        let value = self.arithmetic(i, ADD, a, b)?;
+        self.write(&mut event, &mode, value)?;
+      }
+      Opcode::Mul(mode) => {
+        let (a, b) = (self.arg_value(i, &mode, 0)?, self.arg_value(i, &mode, 1)?);
+        event.read(a);
+        event.read(b);
+        let value = self.arithmetic(i, MUL, a, b)?;
+        self.write(&mut event, &mode, value)?;
+      }
+      Opcode::LessThan(mode) => {
+        let (a, b) = (self.arg_value(i, &mode, 0)?, self.arg_value(i, &mode, 1)?);
+        event.read(a);
+        event.read(b);
+        self.write(&mut event, &mode, (a < b) as i64)?;
+      }
+      Opcode::Equals(mode) => {
+        let (a, b) = (self.arg_value(i, &mode, 0)?, self.arg_value(i, &mode, 1)?);
+        event.read(a);
+        event.read(b);
+        self.write(&mut event, &mode, (a == b) as i64)?;
+      }
+      Opcode::Input(mode) => match self.input.front() {
+        Some(&value) => {
+          self.write(&mut event, &mode, value)?;
+          self.input.pop_front();
         }
-        Opcode::LessThan(mode) => {
-          let value = self.arg_value(&mode, 0) < self.arg_value(&mode, 1);
-          self.write(&mode, value as i64);
+        None => return Ok(Step::NeedsInput),
+      },
+      Opcode::Output(mode) => event.read(self.arg_value(i, &mode, 0)?),
+      Opcode::JumpIfTrue(mode) => {
+        let value = self.arg_value(i, &mode, 0)?;
+        event.read(value);
+        self.jump(&mut event, value != 0, &mode)?;
+      }
+      Opcode::JumpIfFalse(mode) => {
+        let value = self.arg_value(i, &mode, 0)?;
+        event.read(value);
+        self.jump(&mut event, value == 0, &mode)?;
+      }
+      Opcode::RelativeBase(mode) => {
+        let value = self.arg_value(i, &mode, 0)?;
+        event.read(value);
+        self.relative_base = match self.relative_base.checked_add(value) {
+          Some(relative_base) => relative_base,
+          None => {
+            return Err(IntcodeError::RelativeOverflow {
+              ptr: self.ptr,
+              instruction: i,
+              operand: 0,
+            })
+          }
+        };
+      }
+      Opcode::Exit => {
+        if let Some(profile) = self.profile.as_mut() {
+          profile.record_halt(self.ptr);
         }
-        Opcode::Equals(mode) => {
-          let value = self.arg_value(&mode, 0) == self.arg_value(&mode, 1);
-          self.write(&mode, value as i64);
+        if let Some(coverage) = self.coverage.as_mut() {
+          coverage.record_halt(self.ptr);
         }
-        Opcode::Input(mode) => {
-          let value = input.next().expect("not enough input");
-          self.write(&mode, value);
+        if let Some(recording) = self.recording.as_mut() {
+          recording.record_halt();
         }
-        Opcode::Output(mode) => {
-          let res = self.arg_value(&mode, 0);
-          self.ptr += mode.len() + 1;
-          return Some(res);
+        return Ok(Step::Halted);
+      }
+    }
+    if let Some(profile) = self.profile.as_mut() {
+      profile.record(&self.memory, relative_base, &event);
+    }
+    if let Some(coverage) = self.coverage.as_mut() {
+      coverage.record(&self.memory, relative_base, &event);
+    }
+    if let Some(recording) = self.recording.as_mut() {
+      recording.record(&event);
+    }
+    if let Some(history) = self.history.as_mut() {
+      history.record(relative_base, &event);
+    }
+    if !event.jumped {
+      self.ptr += opcode.size();
+    }
+    Ok(Step::Executed(event))
+  }
+
+  // Waiting for input or producing output is not a cycle, since the program makes observable
+  // progress, and new input makes old checkpoints meaningless
+  fn reset_loops(&mut self) {
+    if let Some(detector) = self.loops.as_mut() {
+      *detector = LoopDetector::default();
+    }
+  }
+
+  fn looped(&mut self) -> bool {
+    let detector = match self.loops.as_mut() {
+      Some(detector) => detector,
+      None => return false,
+    };
+    if let Some(checkpoint) = &detector.checkpoint {
+      if checkpoint.ptr == self.ptr
+        && checkpoint.relative_base == self.relative_base
+        && checkpoint.input == self.input
+        && checkpoint.memory == self.memory
+      {
+        return true;
+      }
+    }
+    // Brent's algorithm: moving the checkpoint after 1, 2, 4, ... steps finds any cycle at most
+    // twice its length after entering it, without remembering every state.
+    detector.steps += 1;
+    if detector.steps >= detector.period {
+      detector.checkpoint = Some(Checkpoint {
+        ptr: self.ptr,
+        relative_base: self.relative_base,
+        input: self.input.clone(),
+        memory: self.memory.clone(),
+      });
+      detector.steps = 0;
+      detector.period = (detector.period * 2).max(1);
+    }
+    false
+  }
+
+  pub fn run(&mut self) -> Result<State, IntcodeError> {
+    loop {
+      // A halted program reports the halt even with no budget left
+      if self.budget == Some(0) && !self.is_halted() {
+        return Ok(State::BudgetExhausted);
+      }
+      if self.looped() {
+        return Ok(State::LoopDetected);
+      }
+      let step = self.step()?;
+      if let (Step::Executed(_), Some(budget)) = (&step, self.budget.as_mut()) {
+        *budget -= 1;
+      }
+      match step {
+        Step::Executed(event) => {
+          if let Some(value) = event.output() {
+            self.reset_loops();
+            return Ok(State::Output(value));
+          }
         }
-        Opcode::JumpIfTrue(mode) => self.jump(self.arg_value(&mode, 0) != 0, &mode),
-        Opcode::JumpIfFalse(mode) => self.jump(self.arg_value(&mode, 0) == 0, &mode),
-        Opcode::RelativeBase(mode) => {
-          self.relative_base += self.arg_value(&mode, 0);
-          self.ptr += mode.len() + 1;
+        Step::NeedsInput => {
+          self.reset_loops();
+          return Ok(State::NeedsInput);
         }
-        Opcode::Exit => return None,
+        Step::Halted => return Ok(State::Halted),
+      }
+    }
+  }
+
+  pub fn run_until_blocked(&mut self) -> Result<State, IntcodeError> {
+    loop {
+      match self.run()? {
+        State::Output(value) => self.output.push(value),
+        state => return Ok(state),
+      }
+    }
+  }
+
+  pub fn run_until_outputs(&mut self, n: usize) -> Result<Option<Vec<i64>>, IntcodeError> {
+    while self.output.len() < n {
+      match self.run()? {
+        State::Output(value) => self.output.push(value),
+        _ => return Ok(None),
       }
     }
+    Ok(Some(self.output.drain(..n).collect()))
   }
 
-  pub fn code(&self) -> &[i64] {
-    &self.code
+  pub(crate) fn blocked(&self, state: State) -> IntcodeError {
+    let (ptr, instruction) = (self.ptr, self.memory.get(self.ptr));
+    match state {
+      State::BudgetExhausted => IntcodeError::BudgetExhausted { ptr, instruction },
+      State::LoopDetected => IntcodeError::InfiniteLoop { ptr, instruction },
+      _ => IntcodeError::MissingInput { ptr, instruction },
+    }
+  }
+
+  pub fn run_to_halt(&mut self) -> Result<Vec<i64>, IntcodeError> {
+    match self.run_until_blocked()? {
+      State::Halted => Ok(self.output.drain(..).collect()),
+      state => Err(self.blocked(state)),
+    }
+  }
+
+  pub fn ptr(&self) -> usize {
+    self.ptr
+  }
+
+  pub fn set_ptr(&mut self, ptr: usize) {
+    self.ptr = ptr;
+  }
+
+  pub fn relative_base(&self) -> i64 {
+    self.relative_base
+  }
+
+  pub fn set_relative_base(&mut self, relative_base: i64) {
+    self.relative_base = relative_base;
+  }
+
+  pub fn is_halted(&self) -> bool {
+    self.memory.get(self.ptr) % 100 == 99
+  }
+
+  pub fn memory(&self) -> &Memory {
+    &self.memory
+  }
+
+  pub fn memory_mut(&mut self) -> &mut Memory {
+    if let Some(cache) = self.cache.as_mut() {
+      *cache = Rc::default();
+    }
+    &mut self.memory
+  }
+
+  /// Unlike going through `memory_mut`, this only forgets the decoded instruction at `address`
+  pub fn set_cell(&mut self, address: usize, value: i64) -> Result<(), MemoryLimit> {
+    self.memory.set(address, value)?;
+    self.invalidate(address);
+    Ok(())
   }
 
   pub fn into_code(self) -> Vec<i64> {
-    self.code
+    self.memory.to_vec()
+  }
+}
+
+// Two programs are equal when they are in the same execution state. Settings such as the budget,
+// arithmetic policy, decode cache, profiler, coverage, recorder or history are not compared.
+impl PartialEq for Program {
+  fn eq(&self, other: &Program) -> bool {
+    self.ptr == other.ptr
+      && self.relative_base == other.relative_base
+      && self.input == other.input
+      && self.output == other.output
+      && self.memory == other.memory
+  }
+}
+
+impl Eq for Program {}
+
+impl Hash for Program {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.ptr.hash(state);
+    self.relative_base.hash(state);
+    self.input.hash(state);
+    self.output.hash(state);
+    self.memory.hash(state);
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Event {
+  pub ptr: usize,
+  pub instruction: i64,
+  pub opcode: Opcode,
+  reads: [i64; 2],
+  read_count: usize,
+  pub write: Option<(usize, i64)>,
+  overwritten: i64,
+  pub jumped: bool,
+}
+
+impl Event {
+  fn read(&mut self, value: i64) {
+    self.reads[self.read_count] = value;
+    self.read_count += 1;
+  }
+
+  pub fn reads(&self) -> &[i64] {
+    &self.reads[..self.read_count]
+  }
+
+  pub fn output(&self) -> Option<i64> {
+    match self.opcode {
+      Opcode::Output(_) => Some(self.reads[0]),
+      _ => None,
+    }
+  }
+
+  /// The value the written cell held before this instruction
+  pub fn overwritten(&self) -> Option<i64> {
+    self.write.map(|_| self.overwritten)
+  }
+
+  // The operands as they were before the instruction ran, which matters when it overwrites one
+  pub(crate) fn operands(&self, memory: &Memory) -> Vec<i64> {
+    (self.ptr + 1..self.ptr + self.opcode.size())
+      .map(|address| match self.write {
+        Some((to, _)) if to == address => self.overwritten,
+        _ => memory.get(address),
+      })
+      .collect()
+  }
+
+  // The cells read through position or relative operands, given the relative base the
+  // instruction ran with
+  pub(crate) fn read_addresses(&self, memory: &Memory, relative_base: i64) -> Vec<usize> {
+    let modes = self.opcode.modes().iter().take(self.read_count);
+    modes
+      .zip(self.operands(memory))
+      .filter_map(|(mode, operand)| match mode {
+        ParamMode::Position => Some(operand as usize),
+        ParamMode::Relative => Some((relative_base + operand) as usize),
+        ParamMode::Immediate => None,
+      })
+      .collect()
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Step {
+  Executed(Event),
+  NeedsInput,
+  Halted,
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum State {
+  Output(i64),
+  NeedsInput,
+  Halted,
+  BudgetExhausted,
+  LoopDetected,
+}
+
+impl State {
+  pub fn output(self) -> Option<i64> {
+    match self {
+      State::Output(value) => Some(value),
+      _ => None,
+    }
   }
 }
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
-enum Opcode {
+pub enum IntcodeError {
+  UnknownOpcode {
+    ptr: usize,
+    instruction: i64,
+  },
+  UnknownParamMode {
+    ptr: usize,
+    instruction: i64,
+    operand: usize,
+    mode: i64,
+  },
+  MissingInput {
+    ptr: usize,
+    instruction: i64,
+  },
+  ImmediateWrite {
+    ptr: usize,
+    instruction: i64,
+    operand: usize,
+  },
+  InvalidAddress {
+    ptr: usize,
+    instruction: i64,
+    operand: usize,
+    address: i64,
+  },
+  MemoryLimit {
+    ptr: usize,
+    instruction: i64,
+    operand: usize,
+    address: usize,
+  },
+  Overflow {
+    ptr: usize,
+    instruction: i64,
+  },
+  RelativeOverflow {
+    ptr: usize,
+    instruction: i64,
+    operand: usize,
+  },
+  BudgetExhausted {
+    ptr: usize,
+    instruction: i64,
+  },
+  InfiniteLoop {
+    ptr: usize,
+    instruction: i64,
+  },
+}
+
+impl IntcodeError {
+  pub fn ptr(&self) -> usize {
+    match *self {
+      IntcodeError::UnknownOpcode { ptr, .. }
+      | IntcodeError::UnknownParamMode { ptr, .. }
+      | IntcodeError::MissingInput { ptr, .. }
+      | IntcodeError::ImmediateWrite { ptr, .. }
+      | IntcodeError::InvalidAddress { ptr, .. }
+      | IntcodeError::MemoryLimit { ptr, .. }
+      | IntcodeError::Overflow { ptr, .. }
+      | IntcodeError::RelativeOverflow { ptr, .. }
+      | IntcodeError::BudgetExhausted { ptr, .. }
+      | IntcodeError::InfiniteLoop { ptr, .. } => ptr,
+    }
+  }
+
+  pub fn instruction(&self) -> i64 {
+    match *self {
+      IntcodeError::UnknownOpcode { instruction, .. }
+      | IntcodeError::UnknownParamMode { instruction, .. }
+      | IntcodeError::MissingInput { instruction, .. }
+      | IntcodeError::ImmediateWrite { instruction, .. }
+      | IntcodeError::InvalidAddress { instruction, .. }
+      | IntcodeError::MemoryLimit { instruction, .. }
+      | IntcodeError::Overflow { instruction, .. }
+      | IntcodeError::RelativeOverflow { instruction, .. }
+      | IntcodeError::BudgetExhausted { instruction, .. }
+      | IntcodeError::InfiniteLoop { instruction, .. } => instruction,
+    }
+  }
+}
+
+impl fmt::Display for IntcodeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      IntcodeError::UnknownOpcode { instruction, .. } => {
+        write!(f, "wrong opcode {}", instruction % 100)?
+      }
+      IntcodeError::UnknownParamMode { operand, mode, .. } => {
+        write!(f, "unknown param mode {} for operand {}", mode, operand)?
+      }
+      IntcodeError::MissingInput { .. } => write!(f, "not enough input")?,
+      IntcodeError::ImmediateWrite { operand, .. } => {
+        write!(f, "write to immediate operand {}", operand)?
+      }
+      IntcodeError::InvalidAddress {
+        operand, address, ..
+      } => write!(f, "invalid address {} in operand {}", address, operand)?,
+      IntcodeError::MemoryLimit {
+        operand, address, ..
+      } => write!(
+        f,
+        "address {} in operand {} exceeds memory limit",
+        address, operand
+      )?,
+      IntcodeError::Overflow { .. } => write!(f, "arithmetic overflow")?,
+      IntcodeError::RelativeOverflow { operand, .. } => {
+        write!(f, "relative base overflow in operand {}", operand)?
+      }
+      IntcodeError::BudgetExhausted { .. } => write!(f, "instruction budget exhausted")?,
+      IntcodeError::InfiniteLoop { .. } => write!(f, "infinite loop")?,
+    }
+    write!(f, " at {} ({})", self.ptr(), self.instruction())
+  }
+}
+
+impl std::error::Error for IntcodeError {}
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum Opcode {
   Add([ParamMode; 3]),
   Mul([ParamMode; 3]),
   Input([ParamMode; 1]),
@@ -112,54 +828,479 @@ enum Opcode {
   Exit,
 }
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
-enum ParamMode {
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum ParamMode {
   Position,
   Immediate,
   Relative,
 }
 
+impl Opcode {
+  pub fn mnemonic(&self) -> &'static str {
+    match self {
+      Opcode::Add(_) => "add",
+      Opcode::Mul(_) => "mul",
+      Opcode::Input(_) => "in",
+      Opcode::Output(_) => "out",
+      Opcode::JumpIfTrue(_) => "jt",
+      Opcode::JumpIfFalse(_) => "jf",
+      Opcode::LessThan(_) => "lt",
+      Opcode::Equals(_) => "eq",
+      Opcode::RelativeBase(_) => "arb",
+      Opcode::Exit => "hlt",
+    }
+  }
+
+  pub fn modes(&self) -> &[ParamMode] {
+    match self {
+      Opcode::Add(mode) | Opcode::Mul(mode) | Opcode::LessThan(mode) | Opcode::Equals(mode) => mode,
+      Opcode::JumpIfTrue(mode) | Opcode::JumpIfFalse(mode) => mode,
+      Opcode::Input(mode) | Opcode::Output(mode) | Opcode::RelativeBase(mode) => mode,
+      Opcode::Exit => &[],
+    }
+  }
+
+  pub fn size(&self) -> usize {
+    self.modes().len() + 1
+  }
+
+  /// The shortest instruction word that decodes to this opcode
+  pub fn encode(&self) -> i64 {
+    let base = match self {
+      Opcode::Add(_) => 1,
+      Opcode::Mul(_) => 2,
+      Opcode::Input(_) => 3,
+      Opcode::Output(_) => 4,
+      Opcode::JumpIfTrue(_) => 5,
+      Opcode::JumpIfFalse(_) => 6,
+      Opcode::LessThan(_) => 7,
+      Opcode::Equals(_) => 8,
+      Opcode::RelativeBase(_) => 9,
+      Opcode::Exit => 99,
+    };
+    let modes = self.modes().iter().enumerate();
+    base
+      + modes
+        .map(|(idx, mode)| mode.digit() * 10i64.pow(idx as u32 + 2))
+        .sum::<i64>()
+  }
+
+  pub fn writes(&self) -> bool {
+    matches!(
+      self,
+      Opcode::Add(_) | Opcode::Mul(_) | Opcode::Input(_) | Opcode::LessThan(_) | Opcode::Equals(_)
+    )
+  }
+}
+
+impl ParamMode {
+  pub fn digit(self) -> i64 {
+    match self {
+      ParamMode::Position => 0,
+      ParamMode::Immediate => 1,
+      ParamMode::Relative => 2,
+    }
+  }
+
+  pub fn sigil(self) -> &'static str {
+    match self {
+      ParamMode::Position => "",
+      ParamMode::Immediate => "#",
+      ParamMode::Relative => "@",
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum DecodeError {
+  Opcode,
+  ParamMode { operand: usize, mode: i64 },
+}
+
 #[inline]
-fn arg_mode_immediate(instruction: i64, arg: u32) -> ParamMode {
+fn arg_mode_immediate(instruction: i64, arg: u32) -> Result<ParamMode, DecodeError> {
   let decimal_pos = 10i64.pow(arg + 2);
   match (instruction / decimal_pos) % 10 {
-    0 => ParamMode::Position,
-    1 => ParamMode::Immediate,
-    2 => ParamMode::Relative,
-    i => panic!("unknown param mode {} ({})", i, instruction),
+    0 => Ok(ParamMode::Position),
+    1 => Ok(ParamMode::Immediate),
+    2 => Ok(ParamMode::Relative),
+    mode => Err(DecodeError::ParamMode {
+      operand: arg as usize,
+      mode,
+    }),
   }
 }
 
 macro_rules! arg_mode {
   ($i:expr => $($x:expr),*) => ([
-    $(arg_mode_immediate($i, $x)),*
+    $(arg_mode_immediate($i, $x)?),*
   ]);
 }
 
-impl From<i64> for Opcode {
-  fn from(instruction: i64) -> Self {
-    fn arg1(i: i64) -> [ParamMode; 1] {
-      arg_mode![i => 0]
+impl TryFrom<i64> for Opcode {
+  type Error = DecodeError;
+
+  fn try_from(instruction: i64) -> Result<Self, Self::Error> {
+    fn arg1(i: i64) -> Result<[ParamMode; 1], DecodeError> {
+      Ok(arg_mode![i => 0])
     }
-    fn arg2(i: i64) -> [ParamMode; 2] {
-      arg_mode![i => 0, 1]
+    fn arg2(i: i64) -> Result<[ParamMode; 2], DecodeError> {
+      Ok(arg_mode![i => 0, 1])
     }
-    fn arg3(i: i64) -> [ParamMode; 3] {
-      arg_mode![i => 0, 1, 2]
+    fn arg3(i: i64) -> Result<[ParamMode; 3], DecodeError> {
+      Ok(arg_mode![i => 0, 1, 2])
     }
 
-    match instruction % 100 {
-      1 => Opcode::Add(arg3(instruction)),
-      2 => Opcode::Mul(arg3(instruction)),
-      3 => Opcode::Input(arg1(instruction)),
-      4 => Opcode::Output(arg1(instruction)),
-      5 => Opcode::JumpIfTrue(arg2(instruction)),
-      6 => Opcode::JumpIfFalse(arg2(instruction)),
-      7 => Opcode::LessThan(arg3(instruction)),
-      8 => Opcode::Equals(arg3(instruction)),
-      9 => Opcode::RelativeBase(arg1(instruction)),
+    Ok(match instruction % 100 {
+      1 => Opcode::Add(arg3(instruction)?),
+      2 => Opcode::Mul(arg3(instruction)?),
+      3 => Opcode::Input(arg1(instruction)?),
+      4 => Opcode::Output(arg1(instruction)?),
+      5 => Opcode::JumpIfTrue(arg2(instruction)?),
+      6 => Opcode::JumpIfFalse(arg2(instruction)?),
+      7 => Opcode::LessThan(arg3(instruction)?),
+      8 => Opcode::Equals(arg3(instruction)?),
+      9 => Opcode::RelativeBase(arg1(instruction)?),
       99 => Opcode::Exit,
-      i => panic!("wrong opcode {}! ({})", i, instruction),
+      _ => return Err(DecodeError::Opcode),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fixtures::DOUBLER;
+  use crate::memory::DEFAULT_MEMORY_LIMIT;
+
+  fn run_err(code: &[i64]) -> IntcodeError {
+    Program::new(code.to_vec()).run().unwrap_err()
+  }
+
+  #[test]
+  fn unknown_opcode() {
+    assert_eq!(
+      run_err(&[1101, 1, 1, 5, 42, 0]),
+      IntcodeError::UnknownOpcode {
+        ptr: 4,
+        instruction: 42,
+      }
+    );
+  }
+
+  #[test]
+  fn unknown_param_mode() {
+    assert_eq!(
+      run_err(&[3101, 1, 1, 5, 99]),
+      IntcodeError::UnknownParamMode {
+        ptr: 0,
+        instruction: 3101,
+        operand: 1,
+        mode: 3,
+      }
+    );
+  }
+
+  #[test]
+  fn memory_limit() {
+    let mut program = Program::new(&[1101, 1, 1, 100, 99i64] as &[i64]);
+    program.memory_mut().set_limit(64);
+    assert_eq!(
+      program.run(),
+      Err(IntcodeError::MemoryLimit {
+        ptr: 0,
+        instruction: 1101,
+        operand: 2,
+        address: 100,
+      })
+    );
+  }
+
+  #[test]
+  fn far_relative_base() {
+    let mut program = Program::new(&[109, 1_000_000, 21101, 3, 4, 0, 204, 0, 99i64] as &[i64]);
+    assert_eq!(program.run(), Ok(State::Output(7)));
+    assert_eq!(program.memory().get(1_000_000), 7);
+  }
+
+  #[test]
+  fn single_step() {
+    let mut program = Program::new(&[1001, 7, 5, 7, 1105, 1, 0, 3i64] as &[i64]);
+    let event = match program.step() {
+      Ok(Step::Executed(event)) => event,
+      step => panic!("unexpected step {:?}", step),
+    };
+    assert_eq!(event.ptr, 0);
+    assert_eq!(event.opcode.mnemonic(), "add");
+    assert_eq!(event.reads(), &[3, 5]);
+    assert_eq!(event.write, Some((7, 8)));
+    assert!(!event.jumped);
+    assert_eq!(program.ptr(), 4);
+
+    let event = match program.step() {
+      Ok(Step::Executed(event)) => event,
+      step => panic!("unexpected step {:?}", step),
+    };
+    assert_eq!(event.opcode.mnemonic(), "jt");
+    assert_eq!(event.reads(), &[1, 0]);
+    assert_eq!(event.write, None);
+    assert!(event.jumped);
+    assert_eq!(program.ptr(), 0);
+  }
+
+  #[test]
+  fn relative_base() {
+    let mut program = Program::new(&[109, 19, 109, -4, 99i64] as &[i64]);
+    program.step().unwrap();
+    assert_eq!(program.relative_base(), 19);
+    program.step().unwrap();
+    assert_eq!(program.relative_base(), 15);
+    assert_eq!(program.step(), Ok(Step::Halted));
+  }
+
+  #[test]
+  fn self_modifying_code() {
+    let code = crate::asm::assemble(
+      "
+      start: out #1
+             jf flag, #patch
+             hlt
+      patch: add #99, #0, start
+             add #1, #0, flag
+             jt #1, #start
+      flag:  .data 0
+      ",
+    )
+    .unwrap();
+    for &cached in &[true, false] {
+      let mut program = Program::new(code.clone());
+      program.set_decode_cache(cached);
+      assert_eq!(program.run_to_halt(), Ok(vec![1]));
+    }
+  }
+
+  #[test]
+  fn parks_on_missing_input() {
+    let mut program = Program::new(&[3, 0, 4, 0, 99i64] as &[i64]);
+    assert_eq!(program.run(), Ok(State::NeedsInput));
+    assert_eq!(program.run(), Ok(State::NeedsInput));
+    program.push_input(7);
+    assert_eq!(program.run(), Ok(State::Output(7)));
+    assert_eq!(program.run(), Ok(State::Halted));
+    assert_eq!(program.run(), Ok(State::Halted));
+  }
+
+  #[test]
+  fn keeps_unconsumed_input() {
+    let mut program = Program::new(&[3, 0, 4, 0, 3, 0, 4, 0, 99i64] as &[i64]);
+    program.extend_input(vec![1, 2, 3]);
+    assert_eq!(program.run(), Ok(State::Output(1)));
+    assert_eq!(program.input(), &[2, 3]);
+    assert_eq!(program.run_to_halt(), Ok(vec![2]));
+    assert_eq!(program.input(), &[3]);
+  }
+
+  #[test]
+  fn run_until_outputs() {
+    let mut program = Program::new(&[104, 1, 104, 2, 104, 3, 99i64] as &[i64]);
+    assert_eq!(program.run_until_outputs(2), Ok(Some(vec![1, 2])));
+    assert_eq!(program.run_until_outputs(2), Ok(None));
+    assert_eq!(program.drain_output().collect::<Vec<_>>(), vec![3]);
+  }
+
+  #[test]
+  fn run_to_halt_without_input() {
+    let mut program = Program::new(&[104, 5, 3, 0, 99i64] as &[i64]);
+    assert_eq!(
+      program.run_to_halt(),
+      Err(IntcodeError::MissingInput {
+        ptr: 2,
+        instruction: 3,
+      })
+    );
+    assert_eq!(program.output(), &[5]);
+  }
+
+  #[test]
+  fn budget() {
+    // out #7; jt #1, #0
+    let mut program = Program::new(vec![104, 7, 1105, 1, 0]);
+    program.set_budget(Some(4));
+    assert_eq!(program.run(), Ok(State::Output(7)));
+    assert_eq!(program.run(), Ok(State::Output(7)));
+    assert_eq!(program.run(), Ok(State::BudgetExhausted));
+    assert_eq!(program.budget(), Some(0));
+    assert_eq!(program.ptr(), 0);
+    program.set_budget(Some(1));
+    assert_eq!(program.run(), Ok(State::Output(7)));
+    assert_eq!(
+      program.run_to_halt(),
+      Err(IntcodeError::BudgetExhausted {
+        ptr: 2,
+        instruction: 1105,
+      })
+    );
+
+    let mut program = Program::new(vec![99]);
+    assert_eq!(program.run(), Ok(State::Halted));
+    program.set_budget(Some(0));
+    assert_eq!(program.run(), Ok(State::Halted));
+  }
+
+  #[test]
+  fn loop_detection() {
+    // add 7, #1, 7; jt 8, #0; .data 0, 1
+    let mut program = Program::new(vec![1001, 7, 1, 7, 1005, 8, 0, 0, 1]);
+    program.set_budget(Some(10_000));
+    program.set_loop_detection(true);
+    assert_eq!(program.run(), Ok(State::BudgetExhausted));
+
+    // add #0, #0, 7; jt #1, #0; .data 0
+    let mut program = Program::new(vec![1101, 0, 0, 7, 1105, 1, 0, 0]);
+    program.set_loop_detection(true);
+    assert_eq!(
+      program.run_to_halt(),
+      Err(IntcodeError::InfiniteLoop {
+        ptr: 4,
+        instruction: 1105,
+      })
+    );
+  }
+
+  #[test]
+  fn set_cell() {
+    // out #1; hlt, patched into out 1
+    let mut program = Program::new(vec![104, 1, 99, 0, 99]);
+    assert_eq!(program.run_to_halt(), Ok(vec![1]));
+    program.set_cell(2, 4).unwrap();
+    program.set_cell(3, 1).unwrap();
+    program.set_ptr(0);
+    assert_eq!(program.run_to_halt(), Ok(vec![1, 1]));
+    assert!(program.set_cell(DEFAULT_MEMORY_LIMIT, 1).is_err());
+  }
+
+  #[test]
+  fn relative_base_overflow() {
+    // arb #MAX; arb #1
+    assert_eq!(
+      Program::new(vec![109, i64::MAX, 109, 1, 99]).run_to_halt(),
+      Err(IntcodeError::RelativeOverflow {
+        ptr: 2,
+        instruction: 109,
+        operand: 0,
+      })
+    );
+    // arb #MAX; out @1
+    assert_eq!(
+      Program::new(vec![109, i64::MAX, 204, 1, 99]).run_to_halt(),
+      Err(IntcodeError::RelativeOverflow {
+        ptr: 2,
+        instruction: 204,
+        operand: 0,
+      })
+    );
+  }
+
+  #[test]
+  fn loop_detection_while_waiting() {
+    let mut program = Program::new(vec![3, 0, 4, 0, 99]);
+    program.set_loop_detection(true);
+    assert_eq!(program.run(), Ok(State::NeedsInput));
+    assert_eq!(program.run(), Ok(State::NeedsInput));
+
+    // in 12; eq 12, #-1, 13; jt 13, #0; out 12; hlt; .data 0, 0
+    let mut program = Program::new(vec![3, 12, 1008, 12, -1, 13, 1005, 13, 0, 4, 12, 99, 0, 0]);
+    program.set_loop_detection(true);
+    for _ in 0..8 {
+      assert_eq!(program.run(), Ok(State::NeedsInput));
+      program.push_input(-1);
+    }
+    program.push_input(5);
+    assert_eq!(program.run(), Ok(State::Output(5)));
+  }
+
+  #[test]
+  fn loop_detection_with_output() {
+    // out #7; jt #1, #0
+    let mut program = Program::new(vec![104, 7, 1105, 1, 0]);
+    program.set_loop_detection(true);
+    for _ in 0..10 {
+      assert_eq!(program.run(), Ok(State::Output(7)));
     }
   }
+
+  #[test]
+  fn arithmetic_overflow() {
+    let run = |arithmetic, a: i64, b: i64| {
+      let mut program = Program::new(vec![1102, a, b, 7, 4, 7, 99, 0]);
+      program.set_arithmetic(arithmetic);
+      program.run_to_halt()
+    };
+    assert_eq!(run(Arithmetic::Wrapping, i64::MAX, 2), Ok(vec![-2]));
+    assert_eq!(run(Arithmetic::Saturating, i64::MAX, 2), Ok(vec![i64::MAX]));
+    assert_eq!(run(Arithmetic::Saturating, i64::MIN, 2), Ok(vec![i64::MIN]));
+    assert_eq!(
+      run(Arithmetic::Checked, i64::MAX, 2),
+      Err(IntcodeError::Overflow {
+        ptr: 0,
+        instruction: 1102,
+      })
+    );
+    assert_eq!(
+      run(Arithmetic::Checked, 34915192, 34915192),
+      Ok(vec![1219070632396864])
+    );
+  }
+
+  #[test]
+  fn immediate_write() {
+    assert_eq!(
+      run_err(&[11101, 1, 1, 5, 99]),
+      IntcodeError::ImmediateWrite {
+        ptr: 0,
+        instruction: 11101,
+        operand: 2,
+      }
+    );
+  }
+
+  #[test]
+  fn negative_address() {
+    assert_eq!(
+      run_err(&[4, -1, 99]),
+      IntcodeError::InvalidAddress {
+        ptr: 0,
+        instruction: 4,
+        operand: 0,
+        address: -1,
+      }
+    );
+  }
+
+  #[test]
+  fn fork() {
+    let mut program = Program::new(&DOUBLER[..]);
+    program.push_input(1);
+    assert_eq!(program.run(), Ok(State::Output(2)));
+
+    let mut fork = program.clone();
+    fork.set_budget(Some(10));
+    assert!(fork == program);
+    fork.push_input(5);
+    program.push_input(3);
+    assert!(fork != program);
+    assert_eq!(fork.run(), Ok(State::Output(10)));
+    assert_eq!(program.run(), Ok(State::Output(6)));
+    assert_eq!(program.memory().get(11), 6);
+
+    let mut states = std::collections::HashSet::new();
+    states.insert(program.clone());
+    states.insert(fork);
+    let mut same = Program::new(DOUBLER.to_vec());
+    same.extend_input(vec![1, 3]);
+    assert_eq!(same.run_until_outputs(2), Ok(Some(vec![2, 6])));
+    same.drain_output();
+    assert!(states.contains(&same));
+    assert_eq!(states.len(), 2);
+  }
 }
@@ -0,0 +1,316 @@
+use crate::computer::{Event, Opcode, ParamMode};
+use crate::disasm::{decode_memory, list_with, Item};
+use crate::memory::Memory;
+use crate::profile::percent;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct Branch {
+  pub taken: u64,
+  pub not_taken: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Coverage {
+  // Instructions as they were when they first ran, so self-modifying code is listed correctly
+  executed: BTreeMap<usize, (Item, u64)>,
+  branches: BTreeMap<usize, Branch>,
+  touched: BTreeSet<usize>,
+  halted: bool,
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct Summary {
+  pub instructions: usize,
+  pub executed: usize,
+  pub directions: usize,
+  pub taken: usize,
+  pub cells: usize,
+  pub touched: usize,
+}
+
+impl Summary {
+  pub fn percent(&self) -> f64 {
+    percent(self.executed as u64, self.instructions as u64)
+  }
+}
+
+impl fmt::Display for Summary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(
+      f,
+      "instructions: {}/{} ({:.2}%)",
+      self.executed,
+      self.instructions,
+      self.percent()
+    )?;
+    writeln!(
+      f,
+      "branches: {}/{} ({:.2}%)",
+      self.taken,
+      self.directions,
+      percent(self.taken as u64, self.directions as u64)
+    )?;
+    write!(
+      f,
+      "cells: {}/{} ({:.2}%)",
+      self.touched,
+      self.cells,
+      percent(self.touched as u64, self.cells as u64)
+    )
+  }
+}
+
+// A condition in immediate mode can only ever go one way
+fn directions(opcode: Opcode) -> usize {
+  match opcode {
+    Opcode::JumpIfTrue(mode) | Opcode::JumpIfFalse(mode) if mode[0] == ParamMode::Immediate => 1,
+    Opcode::JumpIfTrue(_) | Opcode::JumpIfFalse(_) => 2,
+    _ => 0,
+  }
+}
+
+impl Coverage {
+  pub(crate) fn record(&mut self, memory: &Memory, relative_base: i64, event: &Event) {
+    let operands = event.operands(memory);
+    if let Opcode::JumpIfTrue(_) | Opcode::JumpIfFalse(_) = event.opcode {
+      let branch = self.branches.entry(event.ptr).or_default();
+      if event.jumped {
+        branch.taken += 1;
+      } else {
+        branch.not_taken += 1;
+      }
+    }
+
+    self
+      .touched
+      .extend(event.read_addresses(memory, relative_base));
+    if let Some((address, _)) = event.write {
+      self.touched.insert(address);
+    }
+    self.record_instruction(event.ptr, event.opcode, operands);
+    self.halted = false;
+  }
+
+  // Like the profile, only count the `hlt` the first time a halted program reports it
+  pub(crate) fn record_halt(&mut self, ptr: usize) {
+    if !self.halted {
+      self.record_instruction(ptr, Opcode::Exit, Vec::new());
+      self.halted = true;
+    }
+  }
+
+  fn record_instruction(&mut self, ptr: usize, opcode: Opcode, operands: Vec<i64>) {
+    let item = Item::Instruction {
+      address: ptr,
+      opcode,
+      operands,
+    };
+    self.executed.entry(ptr).or_insert((item, 0)).1 += 1;
+    self.touched.extend(ptr..ptr + opcode.size());
+  }
+
+  pub fn executions(&self, address: usize) -> u64 {
+    self.executed.get(&address).map_or(0, |&(_, count)| count)
+  }
+
+  pub fn branch(&self, address: usize) -> Branch {
+    self.branches.get(&address).copied().unwrap_or_default()
+  }
+
+  pub fn is_touched(&self, address: usize) -> bool {
+    self.touched.contains(&address)
+  }
+
+  pub fn untouched(&self, memory: &Memory) -> Vec<usize> {
+    (0..memory.len())
+      .filter(|address| !self.is_touched(*address))
+      .collect()
+  }
+
+  // Addresses that ran are instructions. Elsewhere, anything that decodes without overlapping a
+  // touched cell is listed as code that never ran, and the rest as data.
+  fn listing(&self, memory: &Memory) -> Vec<Item> {
+    list_with(
+      memory.len(),
+      |address| memory.get(address),
+      |address| match self.executed.get(&address) {
+        Some((item, _)) => Some(item.clone()),
+        None => decode_memory(memory, address).filter(|item| {
+          (address..address + item.size()).all(|a| a < memory.len() && !self.is_touched(a))
+        }),
+      },
+      |address| self.is_touched(address),
+    )
+  }
+
+  pub fn summary(&self, memory: &Memory) -> Summary {
+    let mut summary = Summary {
+      cells: memory.len(),
+      touched: (0..memory.len()).filter(|&a| self.is_touched(a)).count(),
+      ..Summary::default()
+    };
+    for item in self.listing(memory) {
+      if let Item::Instruction {
+        address, opcode, ..
+      } = item
+      {
+        let branch = self.branch(address);
+        summary.instructions += 1;
+        summary.executed += self.executed.contains_key(&address) as usize;
+        summary.directions += directions(opcode);
+        summary.taken += (branch.taken > 0) as usize + (branch.not_taken > 0) as usize;
+      }
+    }
+    summary
+  }
+
+  fn write_report(&self, out: &mut fmt::Formatter, memory: &Memory) -> fmt::Result {
+    for item in self.listing(memory) {
+      let address = item.address();
+      match &item {
+        Item::Instruction { opcode, .. } => match self.executions(address) {
+          0 => writeln!(out, "{:>10} {}", "#####", item)?,
+          count if directions(*opcode) > 0 => {
+            let branch = self.branch(address);
+            writeln!(
+              out,
+              "{:>10} {:<40} taken {}, not taken {}",
+              count,
+              item.to_string(),
+              branch.taken,
+              branch.not_taken
+            )?
+          }
+          count => writeln!(out, "{:>10} {}", count, item)?,
+        },
+        Item::Data { .. } if self.is_touched(address) => writeln!(out, "{:>10} {}", "-", item)?,
+        Item::Data { .. } => writeln!(out, "{:>10} {}", ".", item)?,
+      }
+    }
+    writeln!(out, "\n{}", self.summary(memory))
+  }
+
+  /// An annotated disassembly: execution counts for instructions that ran, `#####` for those that
+  /// never did, `-` for data that was read or written and `.` for cells nothing touched
+  pub fn report<'a>(&'a self, memory: &'a Memory) -> CoverageReport<'a> {
+    CoverageReport {
+      coverage: self,
+      memory,
+    }
+  }
+}
+
+pub struct CoverageReport<'a> {
+  coverage: &'a Coverage,
+  memory: &'a Memory,
+}
+
+impl fmt::Display for CoverageReport<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.coverage.write_report(f, self.memory)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asm::assemble;
+  use crate::computer::Program;
+
+  fn covered(input: i64) -> Program {
+    let code = assemble(
+      "
+            in x
+            jt x, #odd
+            out #0
+            hlt
+      odd:  out x
+            hlt
+      x:    .data 0
+      y:    .data 7, 7
+      ",
+    )
+    .unwrap();
+    let mut program = Program::new(code);
+    program.set_coverage(true);
+    program.push_input(input);
+    program.run_to_halt().unwrap();
+    program
+  }
+
+  #[test]
+  fn executed_and_touched() {
+    let program = covered(3);
+    let coverage = program.coverage().unwrap();
+    assert_eq!(coverage.executions(0), 1);
+    assert_eq!(coverage.executions(5), 0);
+    assert_eq!(coverage.executions(10), 1);
+    assert_eq!(
+      coverage.branch(2),
+      Branch {
+        taken: 1,
+        not_taken: 0
+      }
+    );
+    assert_eq!(coverage.untouched(program.memory()), vec![5, 6, 7, 12, 13]);
+    assert_eq!(
+      coverage.summary(program.memory()),
+      Summary {
+        instructions: 6,
+        executed: 4,
+        directions: 2,
+        taken: 1,
+        cells: 14,
+        touched: 9,
+      }
+    );
+  }
+
+  #[test]
+  fn self_modifying_operands() {
+    // add 5, #0, 1 overwrites its own first operand with 7
+    let mut program = Program::new(vec![1001, 5, 0, 1, 99, 7, 0, 0]);
+    program.set_coverage(true);
+    program.run_to_halt().unwrap();
+    let coverage = program.coverage().unwrap();
+    assert_eq!(coverage.untouched(program.memory()), vec![6, 7]);
+  }
+
+  #[test]
+  fn halt_counted_once() {
+    let mut program = Program::new(vec![99]);
+    program.set_coverage(true);
+    for _ in 0..3 {
+      program.run().unwrap();
+    }
+    assert_eq!(program.coverage().unwrap().executions(0), 1);
+  }
+
+  #[test]
+  fn report() {
+    let program = covered(0);
+    let report = program
+      .coverage()
+      .unwrap()
+      .report(program.memory())
+      .to_string();
+    assert_eq!(
+      report,
+      "         1     0: in 11
+         1     2: jt 11, #8                         taken 0, not taken 1
+         1     5: out #0
+         1     7: hlt
+     #####     8: out 11
+     #####    10: hlt
+         -    11: .data 0
+         .    12: .data 7, 7
+
+instructions: 4/6 (66.67%)
+branches: 1/2 (50.00%)
+cells: 9/14 (64.29%)
+"
+    );
+  }
+}
@@ -0,0 +1,228 @@
+use crate::computer::{IntcodeError, Program, State};
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+pub trait IoDevice {
+  fn read(&mut self) -> Option<i64>;
+  fn write(&mut self, value: i64);
+}
+
+impl<I: IoDevice, O: IoDevice> IoDevice for (I, O) {
+  fn read(&mut self) -> Option<i64> {
+    self.0.read()
+  }
+
+  fn write(&mut self, value: i64) {
+    self.1.write(value)
+  }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Feeder {
+  values: VecDeque<i64>,
+}
+
+impl Feeder {
+  pub fn new(values: impl IntoIterator<Item = i64>) -> Self {
+    Self {
+      values: values.into_iter().collect(),
+    }
+  }
+}
+
+impl IoDevice for Feeder {
+  fn read(&mut self) -> Option<i64> {
+    self.values.pop_front()
+  }
+
+  fn write(&mut self, _: i64) {}
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Recorder {
+  values: Vec<i64>,
+}
+
+impl Recorder {
+  pub fn values(&self) -> &[i64] {
+    &self.values
+  }
+
+  pub fn into_values(self) -> Vec<i64> {
+    self.values
+  }
+}
+
+impl IoDevice for Recorder {
+  fn read(&mut self) -> Option<i64> {
+    None
+  }
+
+  fn write(&mut self, value: i64) {
+    self.values.push(value);
+  }
+}
+
+#[derive(Default, Debug)]
+pub struct Stdio {
+  pending: VecDeque<i64>,
+}
+
+impl IoDevice for Stdio {
+  fn read(&mut self) -> Option<i64> {
+    let stdin = io::stdin();
+    while self.pending.is_empty() {
+      let mut line = String::new();
+      if stdin.lock().read_line(&mut line).ok()? == 0 {
+        return None;
+      }
+      for word in line.split(|c: char| c == ',' || c.is_whitespace()) {
+        match word.parse() {
+          Ok(value) => self.pending.push_back(value),
+          Err(_) if word.is_empty() => {}
+          Err(_) => eprintln!("ignoring invalid input `{}`", word),
+        }
+      }
+    }
+    self.pending.pop_front()
+  }
+
+  fn write(&mut self, value: i64) {
+    println!("{}", value);
+  }
+}
+
+pub trait Record: Sized {
+  const SIZE: usize;
+
+  fn decode(values: &[i64]) -> Self;
+}
+
+impl Record for (i64, i64) {
+  const SIZE: usize = 2;
+
+  fn decode(values: &[i64]) -> Self {
+    (values[0], values[1])
+  }
+}
+
+impl Record for (i64, i64, i64) {
+  const SIZE: usize = 3;
+
+  fn decode(values: &[i64]) -> Self {
+    (values[0], values[1], values[2])
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Tile {
+  pub x: i64,
+  pub y: i64,
+  pub tile: i64,
+}
+
+impl Record for Tile {
+  const SIZE: usize = 3;
+
+  fn decode(values: &[i64]) -> Self {
+    Tile {
+      x: values[0],
+      y: values[1],
+      tile: values[2],
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Records<T> {
+  buffer: Vec<i64>,
+  records: Vec<T>,
+}
+
+impl<T> Default for Records<T> {
+  fn default() -> Self {
+    Self {
+      buffer: Vec::new(),
+      records: Vec::new(),
+    }
+  }
+}
+
+impl<T: Record> Records<T> {
+  pub fn records(&self) -> &[T] {
+    &self.records
+  }
+
+  pub fn drain(&mut self) -> std::vec::Drain<'_, T> {
+    self.records.drain(..)
+  }
+
+  pub fn partial(&self) -> &[i64] {
+    &self.buffer
+  }
+}
+
+impl<T: Record> IoDevice for Records<T> {
+  fn read(&mut self) -> Option<i64> {
+    None
+  }
+
+  fn write(&mut self, value: i64) {
+    self.buffer.push(value);
+    if self.buffer.len() == T::SIZE {
+      self.records.push(T::decode(&self.buffer));
+      self.buffer.clear();
+    }
+  }
+}
+
+impl Program {
+  pub fn run_with<D: IoDevice + ?Sized>(&mut self, device: &mut D) -> Result<State, IntcodeError> {
+    loop {
+      match self.run()? {
+        State::Output(value) => device.write(value),
+        State::NeedsInput => match device.read() {
+          Some(value) => self.push_input(value),
+          None => return Ok(State::NeedsInput),
+        },
+        state => return Ok(state),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn feeder_and_recorder() {
+    // in 7; out 7; jt #1, #0; .data 0
+    let mut program = Program::new(vec![3, 7, 4, 7, 1105, 1, 0, 0]);
+    let mut device = (Feeder::new(vec![1, 2, 3]), Recorder::default());
+    assert_eq!(program.run_with(&mut device), Ok(State::NeedsInput));
+    assert_eq!(device.1.values(), &[1, 2, 3]);
+  }
+
+  #[test]
+  fn records() {
+    let mut program = Program::new(vec![104, 1, 104, 2, 104, 3, 104, 4, 104, 5, 99]);
+    let mut tiles = Records::<Tile>::default();
+    assert_eq!(program.run_with(&mut tiles), Ok(State::Halted));
+    assert_eq!(
+      tiles.records(),
+      &[Tile {
+        x: 1,
+        y: 2,
+        tile: 3
+      }]
+    );
+    assert_eq!(tiles.partial(), &[4, 5]);
+
+    let mut pairs = Records::<(i64, i64)>::default();
+    pairs.write(7);
+    pairs.write(8);
+    assert_eq!(pairs.drain().collect::<Vec<_>>(), vec![(7, 8)]);
+    assert!(pairs.records().is_empty());
+  }
+}
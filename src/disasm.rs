@@ -0,0 +1,181 @@
+use crate::computer::{Opcode, ParamMode};
+use crate::memory::Memory;
+use std::convert::TryFrom;
+use std::fmt;
+
+const DATA_PER_LINE: usize = 8;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Item {
+  Instruction {
+    address: usize,
+    opcode: Opcode,
+    operands: Vec<i64>,
+  },
+  Data {
+    address: usize,
+    values: Vec<i64>,
+  },
+}
+
+impl Item {
+  pub fn address(&self) -> usize {
+    match *self {
+      Item::Instruction { address, .. } | Item::Data { address, .. } => address,
+    }
+  }
+
+  pub fn size(&self) -> usize {
+    match self {
+      Item::Instruction { opcode, .. } => opcode.size(),
+      Item::Data { values, .. } => values.len(),
+    }
+  }
+}
+
+impl fmt::Display for Item {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:>5}: ", self.address())?;
+    match self {
+      Item::Instruction {
+        opcode, operands, ..
+      } => {
+        write!(f, "{}", opcode.mnemonic())?;
+        for (i, (mode, value)) in opcode.modes().iter().zip(operands).enumerate() {
+          let separator = if i == 0 { " " } else { ", " };
+          write!(f, "{}{}{}", separator, mode.sigil(), value)?;
+        }
+      }
+      Item::Data { values, .. } => {
+        write!(f, ".data")?;
+        for (i, value) in values.iter().enumerate() {
+          let separator = if i == 0 { " " } else { ", " };
+          write!(f, "{}{}", separator, value)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+fn decode_with(get: impl Fn(usize) -> Option<i64>, address: usize) -> Option<Item> {
+  let opcode = Opcode::try_from(get(address)?).ok()?;
+  let operands = (address + 1..address + opcode.size())
+    .map(get)
+    .collect::<Option<Vec<_>>>()?;
+  if opcode.writes() && opcode.modes().last() == Some(&ParamMode::Immediate) {
+    return None;
+  }
+  Some(Item::Instruction {
+    address,
+    opcode,
+    operands,
+  })
+}
+
+pub fn decode_at(code: &[i64], address: usize) -> Option<Item> {
+  decode_with(|address| code.get(address).copied(), address)
+}
+
+pub fn decode_memory(memory: &Memory, address: usize) -> Option<Item> {
+  decode_with(|address| Some(memory.get(address)), address)
+}
+
+// Lists the first `len` cells, using `decode` to find instructions and merging the cells in
+// between into `.data` lines. Data cells only share a line when `group` gives them the same key.
+pub(crate) fn list_with<K: PartialEq>(
+  len: usize,
+  get: impl Fn(usize) -> i64,
+  decode: impl Fn(usize) -> Option<Item>,
+  group: impl Fn(usize) -> K,
+) -> Vec<Item> {
+  let mut items = Vec::new();
+  let mut address = 0;
+  while address < len {
+    let item = decode(address);
+    let size = item.as_ref().map_or(1, Item::size);
+    match (item, items.last_mut()) {
+      (Some(item), _) => items.push(item),
+      (
+        None,
+        Some(Item::Data {
+          address: start,
+          values,
+        }),
+      ) if values.len() < DATA_PER_LINE && group(*start) == group(address) => {
+        values.push(get(address))
+      }
+      (None, _) => items.push(Item::Data {
+        address,
+        values: vec![get(address)],
+      }),
+    }
+    address += size;
+  }
+  items
+}
+
+/// Words with unused mode digits, like 1099, still run, but reassembling them would produce the
+/// canonical word instead, so the listing shows them as data.
+pub fn disassemble(code: &[i64]) -> Vec<Item> {
+  list_with(
+    code.len(),
+    |address| code[address],
+    |address| {
+      decode_at(code, address).filter(|item| match item {
+        Item::Instruction { opcode, .. } => opcode.encode() == code[address],
+        Item::Data { .. } => true,
+      })
+    },
+    |_| (),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn listing(code: &[i64]) -> Vec<String> {
+    disassemble(code)
+      .iter()
+      .map(|item| item.to_string())
+      .collect()
+  }
+
+  #[test]
+  fn instructions() {
+    assert_eq!(
+      listing(&[109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99]),
+      vec![
+        "    0: arb #1",
+        "    2: out @-1",
+        "    4: add 100, #1, 100",
+        "    8: eq 100, #16, 101",
+        "   12: jf 101, #0",
+        "   15: hlt",
+      ]
+    );
+  }
+
+  #[test]
+  fn data() {
+    assert_eq!(
+      listing(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]),
+      vec![
+        "    0: in 9",
+        "    2: eq 9, 10, 9",
+        "    6: out 9",
+        "    8: hlt",
+        "    9: .data -1, 8",
+      ]
+    );
+  }
+
+  #[test]
+  fn truncated_and_immediate_write() {
+    assert_eq!(
+      listing(&[11101, 1, 2, 3, 1, 2]),
+      vec!["    0: .data 11101", "    1: add 2, 3, 1", "    5: .data 2"]
+    );
+  }
+}
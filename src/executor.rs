@@ -0,0 +1,230 @@
+use crate::computer::{IntcodeError, Program, State};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+struct Channel {
+  queue: VecDeque<i64>,
+  waker: Option<Waker>,
+  senders: usize,
+}
+
+impl Channel {
+  fn wake(&mut self) {
+    if let Some(waker) = self.waker.take() {
+      waker.wake();
+    }
+  }
+}
+
+pub struct Sender(Rc<RefCell<Channel>>);
+
+pub struct Receiver(Rc<RefCell<Channel>>);
+
+pub fn channel() -> (Sender, Receiver) {
+  let channel = Rc::new(RefCell::new(Channel {
+    queue: VecDeque::new(),
+    waker: None,
+    senders: 1,
+  }));
+  (Sender(channel.clone()), Receiver(channel))
+}
+
+impl Sender {
+  pub fn send(&self, value: i64) {
+    let mut channel = self.0.borrow_mut();
+    channel.queue.push_back(value);
+    channel.wake();
+  }
+}
+
+impl Clone for Sender {
+  fn clone(&self) -> Self {
+    self.0.borrow_mut().senders += 1;
+    Sender(self.0.clone())
+  }
+}
+
+impl Drop for Sender {
+  fn drop(&mut self) {
+    let mut channel = self.0.borrow_mut();
+    channel.senders -= 1;
+    if channel.senders == 0 {
+      channel.wake();
+    }
+  }
+}
+
+impl Receiver {
+  pub fn recv(&self) -> Recv<'_> {
+    Recv(self)
+  }
+
+  pub fn try_recv(&self) -> Option<i64> {
+    self.0.borrow_mut().queue.pop_front()
+  }
+
+  pub fn len(&self) -> usize {
+    self.0.borrow().queue.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+pub struct Recv<'a>(&'a Receiver);
+
+impl Future for Recv<'_> {
+  type Output = Option<i64>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<i64>> {
+    let mut channel = (self.0).0.borrow_mut();
+    match channel.queue.pop_front() {
+      Some(value) => Poll::Ready(Some(value)),
+      None if channel.senders == 0 => Poll::Ready(None),
+      None => {
+        channel.waker = Some(cx.waker().clone());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+struct TaskWaker {
+  task: usize,
+  ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl Wake for TaskWaker {
+  fn wake(self: Arc<Self>) {
+    self.ready.lock().unwrap().push_back(self.task);
+  }
+}
+
+type Task<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+
+#[derive(Default)]
+pub struct Executor<'a> {
+  tasks: Vec<Option<Task<'a>>>,
+  ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl<'a> Executor<'a> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn spawn(&mut self, future: impl Future<Output = ()> + 'a) {
+    self.ready.lock().unwrap().push_back(self.tasks.len());
+    self.tasks.push(Some(Box::pin(future)));
+  }
+
+  pub fn pending(&self) -> usize {
+    self.tasks.iter().filter(|task| task.is_some()).count()
+  }
+
+  /// Polls tasks until none of them can make progress, then returns how many are still waiting.
+  /// Sending to a channel afterwards wakes its receiver, so `run` can be called again.
+  pub fn run(&mut self) -> usize {
+    loop {
+      let next = self.ready.lock().unwrap().pop_front();
+      let task = match next {
+        Some(task) => task,
+        None => return self.pending(),
+      };
+      let future = match self.tasks[task].as_mut() {
+        Some(future) => future,
+        None => continue,
+      };
+      let waker = Waker::from(Arc::new(TaskWaker {
+        task,
+        ready: self.ready.clone(),
+      }));
+      if future
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+      {
+        self.tasks[task] = None;
+      }
+    }
+  }
+}
+
+impl Program {
+  pub async fn run_async(&mut self, input: &Receiver, output: &Sender) -> Result<(), IntcodeError> {
+    loop {
+      match self.run()? {
+        State::Output(value) => output.send(value),
+        State::NeedsInput => match input.recv().await {
+          Some(value) => self.push_input(value),
+          None => return Err(self.blocked(State::NeedsInput)),
+        },
+        State::Halted => return Ok(()),
+        state => return Err(self.blocked(state)),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fixtures::DOUBLER;
+
+  #[test]
+  fn ping_pong() {
+    let (to_a, from_b) = channel();
+    let (to_b, from_a) = channel();
+    let mut a = Program::new(DOUBLER);
+    let mut b = Program::new(DOUBLER);
+    a.set_budget(Some(100));
+
+    let mut executor = Executor::new();
+    executor.spawn(async {
+      let result = a.run_async(&from_b, &to_b).await;
+      assert!(matches!(result, Err(IntcodeError::BudgetExhausted { .. })));
+    });
+    executor.spawn(async {
+      b.run_async(&from_a, &to_a).await.unwrap();
+    });
+    to_a.send(1);
+    assert_eq!(executor.run(), 1);
+    drop(executor);
+
+    // 25 rounds of in, mul, out and jt, then b's last answer is left for a
+    assert_eq!(from_b.try_recv(), Some(1 << 50));
+    assert!(from_a.is_empty());
+  }
+
+  #[test]
+  fn closed_input() {
+    let (sender, receiver) = channel();
+    let (output, results) = channel();
+    let mut program = Program::new(DOUBLER);
+    let mut executor = Executor::new();
+    executor.spawn(async {
+      let result = program.run_async(&receiver, &output).await;
+      assert_eq!(
+        result,
+        Err(IntcodeError::MissingInput {
+          ptr: 0,
+          instruction: 3
+        })
+      );
+    });
+
+    assert_eq!(executor.run(), 1);
+    sender.send(3);
+    sender.send(4);
+    assert_eq!(executor.run(), 1);
+    assert_eq!((results.try_recv(), results.try_recv()), (Some(6), Some(8)));
+    drop(sender);
+    assert_eq!(executor.run(), 0);
+  }
+}
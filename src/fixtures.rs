@@ -0,0 +1,4 @@
+// Programs shared by the tests of several modules
+
+// in 11; mul 11, #2, 11; out 11; jt #1, #0; .data 0
+pub(crate) static DOUBLER: [i64; 12] = [3, 11, 1002, 11, 2, 11, 4, 11, 1105, 1, 0, 0];
@@ -0,0 +1,359 @@
+use crate::computer::{Opcode, ParamMode};
+use crate::disasm::{decode_at, Item};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Edge {
+  Fallthrough(usize),
+  Jump(usize),
+  Branch(usize),
+  Call { target: usize, ret: usize },
+  Return,
+  Indirect,
+}
+
+impl Edge {
+  pub fn targets(&self) -> Vec<usize> {
+    match *self {
+      Edge::Fallthrough(to) | Edge::Jump(to) | Edge::Branch(to) => vec![to],
+      Edge::Call { target, ret } => vec![target, ret],
+      Edge::Return | Edge::Indirect => Vec::new(),
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Block {
+  pub start: usize,
+  pub items: Vec<Item>,
+  pub edges: Vec<Edge>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ControlFlowGraph {
+  blocks: BTreeMap<usize, Block>,
+}
+
+fn immediate(modes: &[ParamMode], operands: &[i64], idx: usize) -> Option<i64> {
+  match modes[idx] {
+    ParamMode::Immediate => Some(operands[idx]),
+    _ => None,
+  }
+}
+
+// A call stores the return address in a stack slot relative to the relative base right
+// before jumping unconditionally to the function.
+fn is_call(code: &[i64], jump: usize, ret: usize) -> bool {
+  let (opcode, operands) = match jump
+    .checked_sub(4)
+    .and_then(|address| decode_at(code, address))
+  {
+    Some(Item::Instruction {
+      opcode, operands, ..
+    }) => (opcode, operands),
+    _ => return false,
+  };
+  let value = match opcode {
+    Opcode::Add(mode) | Opcode::Mul(mode) if mode[2] == ParamMode::Relative => {
+      match (
+        immediate(&mode, &operands, 0),
+        immediate(&mode, &operands, 1),
+      ) {
+        (Some(a), Some(b)) if matches!(opcode, Opcode::Add(_)) => a.checked_add(b),
+        (Some(a), Some(b)) => a.checked_mul(b),
+        _ => None,
+      }
+    }
+    _ => None,
+  };
+  value == Some(ret as i64)
+}
+
+fn edges(code: &[i64], address: usize, opcode: Opcode, operands: &[i64]) -> Vec<Edge> {
+  let next = address + opcode.size();
+  let (condition, mode) = match opcode {
+    Opcode::JumpIfTrue(mode) => (immediate(&mode, operands, 0).map(|c| c != 0), mode),
+    Opcode::JumpIfFalse(mode) => (immediate(&mode, operands, 0).map(|c| c == 0), mode),
+    Opcode::Exit => return Vec::new(),
+    _ => return vec![Edge::Fallthrough(next)],
+  };
+  let target = match (mode[1], operands[1]) {
+    (ParamMode::Immediate, target) if target >= 0 => Some(Edge::Jump(target as usize)),
+    (ParamMode::Immediate, _) => None,
+    (ParamMode::Relative, _) => Some(Edge::Return),
+    (ParamMode::Position, _) => Some(Edge::Indirect),
+  };
+  match (condition, target) {
+    (Some(false), _) => vec![Edge::Fallthrough(next)],
+    (Some(true), Some(Edge::Jump(target))) if is_call(code, address, next) => {
+      vec![Edge::Call { target, ret: next }]
+    }
+    (Some(true), target) => target.into_iter().collect(),
+    (None, Some(Edge::Jump(target))) => vec![Edge::Branch(target), Edge::Fallthrough(next)],
+    (None, target) => target
+      .into_iter()
+      .chain(Some(Edge::Fallthrough(next)))
+      .collect(),
+  }
+}
+
+pub fn control_flow(code: &[i64]) -> ControlFlowGraph {
+  let mut instructions = BTreeMap::new();
+  let mut pending = vec![0];
+  while let Some(address) = pending.pop() {
+    if instructions.contains_key(&address) {
+      continue;
+    }
+    let item = match decode_at(code, address) {
+      Some(item) => item,
+      None => continue,
+    };
+    let edges = match &item {
+      Item::Instruction {
+        opcode, operands, ..
+      } => edges(code, address, *opcode, operands),
+      Item::Data { .. } => continue,
+    };
+    pending.extend(edges.iter().flat_map(Edge::targets));
+    instructions.insert(address, (item, edges));
+  }
+
+  let mut leaders = BTreeSet::new();
+  leaders.insert(0);
+  for (&address, (item, edges)) in &instructions {
+    if edges.as_slice() != [Edge::Fallthrough(address + item.size())] {
+      leaders.insert(address + item.size());
+    }
+    for edge in edges {
+      if !matches!(edge, Edge::Fallthrough(_)) {
+        leaders.extend(edge.targets());
+      }
+    }
+  }
+
+  let mut blocks = BTreeMap::new();
+  for &start in leaders.iter().filter(|a| instructions.contains_key(a)) {
+    let mut block = Block {
+      start,
+      items: Vec::new(),
+      edges: Vec::new(),
+    };
+    let mut address = start;
+    while let Some((item, edges)) = instructions.get(&address) {
+      block.items.push(item.clone());
+      let next = address + item.size();
+      if edges.as_slice() != [Edge::Fallthrough(next)] {
+        block.edges = edges.clone();
+        break;
+      }
+      if leaders.contains(&next) || !instructions.contains_key(&next) {
+        block.edges = edges.clone();
+        break;
+      }
+      address = next;
+    }
+    blocks.insert(start, block);
+  }
+  ControlFlowGraph { blocks }
+}
+
+impl ControlFlowGraph {
+  pub fn blocks(&self) -> impl Iterator<Item = &Block> {
+    self.blocks.values()
+  }
+
+  pub fn block(&self, start: usize) -> Option<&Block> {
+    self.blocks.get(&start)
+  }
+
+  pub fn functions(&self) -> BTreeSet<usize> {
+    self
+      .blocks()
+      .flat_map(|block| &block.edges)
+      .filter_map(|edge| match *edge {
+        Edge::Call { target, .. } => Some(target),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn write_dot(&self, out: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(out, "digraph intcode {{")?;
+    writeln!(out, "  node [shape=box, fontname=\"monospace\"];")?;
+    let functions = self.functions();
+    let mut returns = false;
+    let mut indirect = false;
+    for block in self.blocks() {
+      let label: String = block
+        .items
+        .iter()
+        .map(|item| format!("{}\\l", item))
+        .collect();
+      let style = if functions.contains(&block.start) {
+        ", style=bold"
+      } else {
+        ""
+      };
+      writeln!(out, "  b{} [label=\"{}\"{}];", block.start, label, style)?;
+
+      let branches = block.edges.len() > 1;
+      for edge in &block.edges {
+        let from = block.start;
+        match *edge {
+          Edge::Fallthrough(to) if branches => {
+            writeln!(out, "  b{} -> b{} [label=\"F\"];", from, to)?
+          }
+          Edge::Fallthrough(to) | Edge::Jump(to) => writeln!(out, "  b{} -> b{};", from, to)?,
+          Edge::Branch(to) => writeln!(out, "  b{} -> b{} [label=\"T\"];", from, to)?,
+          Edge::Call { target, ret } => {
+            writeln!(
+              out,
+              "  b{} -> b{} [label=\"call\", style=dashed];",
+              from, target
+            )?;
+            writeln!(out, "  b{} -> b{} [label=\"ret\"];", from, ret)?;
+          }
+          Edge::Return => {
+            returns = true;
+            writeln!(out, "  b{} -> return;", from)?;
+          }
+          Edge::Indirect => {
+            indirect = true;
+            writeln!(out, "  b{} -> unknown [style=dotted];", from)?;
+          }
+        }
+      }
+    }
+    // Jumps out of the code or into something that does not decode
+    let invalid: BTreeSet<_> = self
+      .blocks()
+      .flat_map(|block| &block.edges)
+      .flat_map(Edge::targets)
+      .filter(|to| !self.blocks.contains_key(to))
+      .collect();
+    for to in invalid {
+      writeln!(out, "  b{} [label=\"{}: invalid\", shape=octagon];", to, to)?;
+    }
+    if returns {
+      writeln!(out, "  return [shape=oval];")?;
+    }
+    if indirect {
+      writeln!(out, "  unknown [shape=diamond, label=\"?\"];")?;
+    }
+    writeln!(out, "}}")
+  }
+
+  pub fn dot(&self) -> Dot<'_> {
+    Dot { graph: self }
+  }
+}
+
+/// The graph in Graphviz DOT format
+pub struct Dot<'a> {
+  graph: &'a ControlFlowGraph,
+}
+
+impl fmt::Display for Dot<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.graph.write_dot(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asm::assemble;
+
+  fn graph() -> ControlFlowGraph {
+    let code = assemble(
+      "
+      main: arb #100
+            add #back, #0, @0
+            jt #1, #func
+      back: in x
+            jt x, #main
+            jt x, x
+            hlt
+      func: arb #1
+            out #7
+            arb #-1
+            jt #1, @0
+      x:    .data 0
+      ",
+    )
+    .unwrap();
+    control_flow(&code)
+  }
+
+  #[test]
+  fn blocks() {
+    let graph = graph();
+    let edges: Vec<_> = graph
+      .blocks()
+      .map(|block| (block.start, block.items.len(), block.edges.clone()))
+      .collect();
+    assert_eq!(
+      edges,
+      vec![
+        (0, 3, vec![Edge::Call { target: 18, ret: 9 }]),
+        (9, 2, vec![Edge::Branch(0), Edge::Fallthrough(14)]),
+        (14, 1, vec![Edge::Indirect, Edge::Fallthrough(17)]),
+        (17, 1, vec![]),
+        (18, 4, vec![Edge::Return]),
+      ]
+    );
+    assert_eq!(graph.functions().into_iter().collect::<Vec<_>>(), vec![18]);
+  }
+
+  #[test]
+  fn dot() {
+    let dot = graph().dot().to_string();
+    assert!(dot.starts_with("digraph intcode {\n"));
+    assert!(dot.contains(
+      "  b0 [label=\"    0: arb #100\\l    2: add #9, #0, @0\\l    6: jt #1, #18\\l\"];\n"
+    ));
+    assert!(dot.contains("  b18 [label=\""));
+    assert!(dot.contains("\", style=bold];\n"));
+    assert!(
+      dot.contains("  b0 -> b18 [label=\"call\", style=dashed];\n  b0 -> b9 [label=\"ret\"];\n")
+    );
+    assert!(dot.contains("  b9 -> b0 [label=\"T\"];\n  b9 -> b14 [label=\"F\"];\n"));
+    assert!(dot.contains("  b14 -> unknown [style=dotted];\n"));
+    assert!(dot.contains("  b18 -> return;\n"));
+    assert!(!dot.contains("invalid"));
+    assert!(dot.ends_with("  return [shape=oval];\n  unknown [shape=diamond, label=\"?\"];\n}\n"));
+  }
+
+  #[test]
+  fn invalid_target() {
+    // jt #1, #50; jt #1, 7
+    let graph = control_flow(&[1105, 1, 50, 1105, 1, 7]);
+    assert_eq!(graph.block(0).unwrap().edges, vec![Edge::Jump(50)]);
+    assert!(graph.block(3).is_none());
+    assert!(graph
+      .dot()
+      .to_string()
+      .contains("  b0 -> b50;\n  b50 [label=\"50: invalid\", shape=octagon];\n"));
+
+    // add #1, #1, 5 runs off the end of the code
+    let graph = control_flow(&[1101, 1, 1, 5]);
+    assert_eq!(graph.block(0).unwrap().edges, vec![Edge::Fallthrough(4)]);
+    assert!(graph
+      .dot()
+      .to_string()
+      .contains("  b0 -> b4;\n  b4 [label=\"4: invalid\", shape=octagon];\n"));
+  }
+  #[test]
+  fn non_canonical_words() {
+    // out 3 with an unused mode digit; hlt; .data 42
+    let graph = control_flow(&[10004, 3, 99, 42]);
+    assert_eq!(graph.block(0).unwrap().items.len(), 2);
+
+    // add #1, #1, 7; jt #1, #8; .data 0; hlt with a mode digit
+    let graph = control_flow(&[1101, 1, 1, 7, 1105, 1, 8, 0, 1099]);
+    assert_eq!(graph.block(0).unwrap().edges, vec![Edge::Jump(8)]);
+    assert_eq!(graph.block(8).unwrap().items.len(), 1);
+    assert!(!graph.dot().to_string().contains("invalid"));
+  }
+}
@@ -0,0 +1,259 @@
+use crate::computer::{Event, Opcode, Program};
+use std::collections::VecDeque;
+
+/// Everything an executed instruction changed, with the values from before it ran. A write is
+/// the address, the value it held before and the value that was stored.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Change {
+  pub ptr: usize,
+  pub relative_base: i64,
+  pub write: Option<(usize, i64, i64)>,
+  pub input: Option<i64>,
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct LastWrite {
+  pub step: usize,
+  pub ptr: usize,
+  pub previous: i64,
+  pub value: i64,
+}
+
+/// With a limit, only the most recent changes are kept and the oldest are dropped
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct History {
+  changes: VecDeque<Change>,
+  limit: Option<usize>,
+  dropped: usize,
+}
+
+impl History {
+  pub(crate) fn record(&mut self, relative_base: i64, event: &Event) {
+    let input = match (event.opcode, event.write) {
+      (Opcode::Input(_), Some((_, value))) => Some(value),
+      _ => None,
+    };
+    self.changes.push_back(Change {
+      ptr: event.ptr,
+      relative_base,
+      write: event
+        .write
+        .map(|(address, value)| (address, event.overwritten().unwrap_or(0), value)),
+      input,
+    });
+    self.trim();
+  }
+
+  fn trim(&mut self) {
+    let limit = self.limit.unwrap_or(usize::MAX);
+    while self.changes.len() > limit {
+      self.changes.pop_front();
+      self.dropped += 1;
+    }
+  }
+
+  pub fn limit(&self) -> Option<usize> {
+    self.limit
+  }
+
+  pub(crate) fn set_limit(&mut self, limit: Option<usize>) {
+    self.limit = limit;
+    self.trim();
+  }
+
+  pub fn len(&self) -> usize {
+    self.changes.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  /// The number of older changes that no longer fit within the limit
+  pub fn dropped(&self) -> usize {
+    self.dropped
+  }
+
+  pub fn changes(&self) -> impl Iterator<Item = &Change> + '_ {
+    self.changes.iter()
+  }
+}
+
+impl Program {
+  /// Undoes the last recorded instruction. Consumed input goes back to the front of the queue,
+  /// but outputs that were already handed out are not taken back.
+  pub fn step_back(&mut self) -> bool {
+    let change = match self
+      .history
+      .as_mut()
+      .and_then(|history| history.changes.pop_back())
+    {
+      Some(change) => change,
+      None => return false,
+    };
+    if let Some((address, previous, _)) = change.write {
+      self.memory.restore(address, previous);
+      self.invalidate(address);
+    }
+    if let Some(value) = change.input {
+      self.input.push_front(value);
+    }
+    self.ptr = change.ptr;
+    self.relative_base = change.relative_base;
+    true
+  }
+
+  /// Steps back at least once, until the instruction at `address` is next. Returns false when the
+  /// history runs out first, leaving the program at the oldest recorded state.
+  pub fn run_back_to(&mut self, address: usize) -> bool {
+    while self.step_back() {
+      if self.ptr == address {
+        return true;
+      }
+    }
+    false
+  }
+
+  pub fn last_write(&self, address: usize) -> Option<LastWrite> {
+    let history = self.history.as_ref()?;
+    let (step, change) = history
+      .changes
+      .iter()
+      .enumerate()
+      .rev()
+      .find(|(_, change)| matches!(change.write, Some((to, _, _)) if to == address))?;
+    let (_, previous, value) = change.write?;
+    Some(LastWrite {
+      step: history.dropped + step,
+      ptr: change.ptr,
+      previous,
+      value,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asm::assemble;
+  use crate::computer::State;
+
+  fn counter() -> Program {
+    let code = assemble(
+      "
+      loop: in x
+            add x, total, total
+            arb x
+            out total
+            jt x, #loop
+            hlt
+      x:    .data 0
+      total: .data 0
+      ",
+    )
+    .unwrap();
+    let mut program = Program::new(code);
+    program.set_history(true);
+    program
+  }
+
+  #[test]
+  fn step_back_restores_state() {
+    let mut program = counter();
+    program.extend_input(vec![3, 4, 0]);
+    let start = program.clone();
+    assert_eq!(program.run(), Ok(State::Output(3)));
+    let after_first = program.clone();
+    assert_eq!(program.run(), Ok(State::Output(7)));
+    assert_eq!(program.relative_base(), 7);
+    assert_eq!(program.history().unwrap().len(), 9);
+
+    for _ in 0..5 {
+      assert!(program.step_back());
+    }
+    assert!(program == after_first);
+    while program.step_back() {}
+    assert!(program == start);
+    assert_eq!(program.input(), &[3, 4, 0]);
+    assert_eq!(program.run_to_halt(), Ok(vec![3, 7, 7]));
+  }
+
+  #[test]
+  fn run_back_to_address() {
+    let mut program = counter();
+    program.extend_input(vec![3, 4, 0]);
+    program.run_to_halt().unwrap();
+    assert!(program.run_back_to(2));
+    assert_eq!(program.memory().get(15), 7);
+    assert!(program.input().is_empty());
+    assert!(program.run_back_to(2));
+    assert_eq!(program.memory().get(15), 3);
+    assert_eq!(program.input(), &[0]);
+    assert!(program.run_back_to(2));
+    assert_eq!(program.memory().get(15), 0);
+    assert!(!program.run_back_to(2));
+    assert_eq!(program.ptr(), 0);
+  }
+
+  #[test]
+  fn last_writer() {
+    let mut program = counter();
+    program.extend_input(vec![3, 4, 0]);
+    program.run_to_halt().unwrap();
+    assert_eq!(
+      program.last_write(15),
+      Some(LastWrite {
+        step: 11,
+        ptr: 2,
+        previous: 7,
+        value: 7,
+      })
+    );
+    assert_eq!(program.last_write(14).map(|write| write.ptr), Some(0));
+    assert_eq!(program.last_write(5), None);
+
+    // The value that write stored, not what the cell holds now
+    program.set_cell(15, 100).unwrap();
+    assert_eq!(program.last_write(15).map(|write| write.value), Some(7));
+  }
+
+  #[test]
+  fn limit() {
+    let mut program = counter();
+    program.set_history_limit(Some(4));
+    program.extend_input(vec![3, 4, 0]);
+    program.run_to_halt().unwrap();
+    let history = program.history().unwrap();
+    assert_eq!((history.len(), history.dropped()), (4, 11));
+    assert_eq!(program.last_write(15).map(|write| write.step), Some(11));
+    assert_eq!(program.last_write(14), None);
+
+    program.set_history_limit(Some(2));
+    assert_eq!(program.history().unwrap().dropped(), 13);
+    assert!(!program.run_back_to(0));
+    assert_eq!(program.ptr(), 8);
+  }
+
+  #[test]
+  fn rewind_self_modifying_code() {
+    // add #100, #4, 4; hlt, patched into out #1
+    let mut program = Program::new(vec![1101, 100, 4, 4, 99, 1, 99]);
+    program.set_history(true);
+    assert_eq!(program.run_to_halt(), Ok(vec![1]));
+    while program.step_back() {}
+    assert_eq!(program.memory().get(4), 99);
+    program.set_ptr(4);
+    assert_eq!(program.run_to_halt(), Ok(vec![]));
+  }
+
+  #[test]
+  fn step_back_past_lowered_limit() {
+    // add #1, #2, 100; hlt
+    let mut program = Program::new(vec![1101, 1, 2, 100, 99]);
+    program.set_history(true);
+    program.run_to_halt().unwrap();
+    program.memory_mut().set_limit(10);
+    assert!(program.step_back());
+    assert_eq!(program.memory().get(100), 0);
+  }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ParseError {
+  pub line: usize,
+  pub column: usize,
+  pub message: String,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}:{}: {}", self.line, self.column, self.message)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn read_input() -> io::Result<String> {
+  match std::env::args().nth(1) {
+    Some(path) if path != "-" => std::fs::read_to_string(path),
+    _ => {
+      let mut input = String::new();
+      io::stdin().read_to_string(&mut input)?;
+      Ok(input)
+    }
+  }
+}
+
+pub fn parse_intcode(source: &str) -> Result<Vec<i64>, ParseError> {
+  let mut code = Vec::new();
+  for (line_idx, line) in source.lines().enumerate() {
+    let mut column = 1;
+    for value in line.split(',') {
+      let trimmed = value.trim();
+      let offset = value.len() - value.trim_start().len();
+      if !trimmed.is_empty() {
+        code.push(trimmed.parse().map_err(|_| ParseError {
+          line: line_idx + 1,
+          column: column + offset,
+          message: format!("invalid intcode value `{}`", trimmed),
+        })?);
+      }
+      column += value.chars().count() + 1;
+    }
+  }
+  Ok(code)
+}
+
+pub fn parse_lines<T: FromStr>(source: &str) -> Result<Vec<T>, ParseError> {
+  source
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(idx, line)| {
+      line.trim().parse().map_err(|_| ParseError {
+        line: idx + 1,
+        column: line.len() - line.trim_start().len() + 1,
+        message: format!("invalid value `{}`", line.trim()),
+      })
+    })
+    .collect()
+}
+
+pub fn parse_range(source: &str) -> Result<RangeInclusive<usize>, ParseError> {
+  let line = source.trim();
+  let error = |column, message: &str| ParseError {
+    line: 1,
+    column,
+    message: message.to_owned(),
+  };
+  let (start, end) = line
+    .split_once('-')
+    .ok_or_else(|| error(1, "expected `<start>-<end>`"))?;
+  let start = start.parse().map_err(|_| error(1, "invalid range start"))?;
+  let end = end
+    .parse()
+    .map_err(|_| error(line.find('-').unwrap_or_default() + 2, "invalid range end"))?;
+  Ok(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn intcode() {
+    assert_eq!(parse_intcode("1,0, 0,3,\n99\n"), Ok(vec![1, 0, 0, 3, 99]));
+    assert_eq!(
+      parse_intcode("1,2,3\n4, x5,6"),
+      Err(ParseError {
+        line: 2,
+        column: 4,
+        message: "invalid intcode value `x5`".to_owned(),
+      })
+    );
+  }
+
+  #[test]
+  fn lines() {
+    assert_eq!(
+      parse_lines::<u32>("12\n14\n\n1969\n"),
+      Ok(vec![12, 14, 1969])
+    );
+    assert_eq!(parse_lines::<u32>("12\n  -4").unwrap_err().column, 3);
+  }
+
+  #[test]
+  fn range() {
+    assert_eq!(parse_range("265275-781584\n"), Ok(265_275..=781_584));
+    assert_eq!(parse_range("12-x").unwrap_err().column, 4);
+  }
+}
@@ -1,5 +1,39 @@
+mod ascii;
+mod asm;
 mod computer;
+mod coverage;
+mod device;
+mod disasm;
+mod executor;
+#[cfg(test)]
+mod fixtures;
+mod flow;
+mod history;
+mod input;
+mod memory;
+mod network;
 mod permuter;
+mod profile;
+mod replay;
+mod snapshot;
+mod symbolic;
+mod transpile;
 
+pub use ascii::*;
+pub use asm::*;
 pub use computer::*;
+pub use coverage::*;
+pub use device::*;
+pub use disasm::*;
+pub use executor::*;
+pub use flow::*;
+pub use history::*;
+pub use input::*;
+pub use memory::*;
+pub use network::*;
 pub use permuter::*;
+pub use profile::*;
+pub use replay::*;
+pub use snapshot::*;
+pub use symbolic::*;
+pub use transpile::*;
@@ -0,0 +1,273 @@
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+const PAGE_SIZE: usize = 1024;
+
+pub const DEFAULT_MEMORY_LIMIT: usize = 1 << 24;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct MemoryLimit {
+  pub address: usize,
+  pub limit: usize,
+}
+
+/// Code a program starts from. A `'static` image is read in place and only the pages written to
+/// get copied; owned code is copied into pages once.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Image {
+  Static(&'static [i64]),
+  Owned(Vec<i64>),
+}
+
+impl From<&'static [i64]> for Image {
+  fn from(code: &'static [i64]) -> Self {
+    Image::Static(code)
+  }
+}
+
+impl<const N: usize> From<&'static [i64; N]> for Image {
+  fn from(code: &'static [i64; N]) -> Self {
+    Image::Static(code)
+  }
+}
+
+impl From<Vec<i64>> for Image {
+  fn from(code: Vec<i64>) -> Self {
+    Image::Owned(code)
+  }
+}
+
+impl<const N: usize> From<[i64; N]> for Image {
+  fn from(code: [i64; N]) -> Self {
+    Image::Owned(code.to_vec())
+  }
+}
+
+/// Pages are shared between clones and copied on their first write, so forking a machine only
+/// costs the page table.
+#[derive(Clone)]
+pub struct Memory {
+  base: &'static [i64],
+  pages: Vec<Option<Rc<[i64; PAGE_SIZE]>>>,
+  pub(crate) len: usize,
+  limit: usize,
+  fingerprint: u64,
+}
+
+// Zobrist-style hash of one cell. Zero cells contribute nothing, so the fingerprint is the XOR
+// over all non-zero cells and can be updated on every write.
+fn mix(address: usize, value: i64) -> u64 {
+  if value == 0 {
+    return 0;
+  }
+  let mut x = (address as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ value as u64;
+  x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  x ^ (x >> 31)
+}
+
+impl Memory {
+  pub fn new(code: &[i64]) -> Self {
+    let mut memory = Self::from_static(&[]);
+    for (page, chunk) in code.chunks(PAGE_SIZE).enumerate() {
+      memory.page_mut(page)[..chunk.len()].copy_from_slice(chunk);
+    }
+    memory.len = code.len();
+    memory.limit = memory.limit.max(code.len());
+    memory.fingerprint = fingerprint(code);
+    memory
+  }
+
+  pub fn from_static(code: &'static [i64]) -> Self {
+    Self {
+      base: code,
+      pages: Vec::new(),
+      len: code.len(),
+      limit: DEFAULT_MEMORY_LIMIT.max(code.len()),
+      fingerprint: fingerprint(code),
+    }
+  }
+
+  pub fn from_image(image: Image) -> Self {
+    match image {
+      Image::Static(code) => Self::from_static(code),
+      Image::Owned(code) => Self::new(&code),
+    }
+  }
+
+  pub fn limit(&self) -> usize {
+    self.limit
+  }
+
+  pub fn set_limit(&mut self, limit: usize) {
+    self.limit = limit;
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn fingerprint(&self) -> u64 {
+    self.fingerprint
+  }
+
+  #[inline]
+  pub fn get(&self, address: usize) -> i64 {
+    match self.pages.get(address / PAGE_SIZE) {
+      Some(Some(page)) => page[address % PAGE_SIZE],
+      _ => self.base.get(address).copied().unwrap_or(0),
+    }
+  }
+
+  #[inline]
+  pub fn set(&mut self, address: usize, value: i64) -> Result<(), MemoryLimit> {
+    if address >= self.limit {
+      return Err(MemoryLimit {
+        address,
+        limit: self.limit,
+      });
+    }
+    self.restore(address, value);
+    Ok(())
+  }
+
+  // Writes without checking the limit, for putting back a value that was already in memory
+  pub(crate) fn restore(&mut self, address: usize, value: i64) {
+    let cell = &mut self.page_mut(address / PAGE_SIZE)[address % PAGE_SIZE];
+    let old = std::mem::replace(cell, value);
+    self.fingerprint ^= mix(address, old) ^ mix(address, value);
+    self.len = self.len.max(address + 1);
+  }
+
+  pub fn to_vec(&self) -> Vec<i64> {
+    (0..self.len).map(|address| self.get(address)).collect()
+  }
+
+  fn page_mut(&mut self, page: usize) -> &mut [i64; PAGE_SIZE] {
+    if page >= self.pages.len() {
+      self.pages.resize_with(page + 1, || None);
+    }
+    let base = self.base;
+    let page = self.pages[page].get_or_insert_with(|| {
+      let mut cells = [0; PAGE_SIZE];
+      let start = (page * PAGE_SIZE).min(base.len());
+      let end = (start + PAGE_SIZE).min(base.len());
+      cells[..end - start].copy_from_slice(&base[start..end]);
+      Rc::new(cells)
+    });
+    Rc::make_mut(page)
+  }
+
+  fn page(&self, page: usize) -> Option<&Rc<[i64; PAGE_SIZE]>> {
+    self.pages.get(page).and_then(Option::as_ref)
+  }
+
+  fn same_page(&self, other: &Memory, page: usize) -> bool {
+    match (self.page(page), other.page(page)) {
+      (Some(a), Some(b)) if Rc::ptr_eq(a, b) => true,
+      (None, None) if std::ptr::eq(self.base, other.base) => true,
+      _ => (page * PAGE_SIZE..(page + 1) * PAGE_SIZE)
+        .all(|address| self.get(address) == other.get(address)),
+    }
+  }
+}
+
+fn fingerprint(code: &[i64]) -> u64 {
+  code
+    .iter()
+    .enumerate()
+    .fold(0, |hash, (address, &value)| hash ^ mix(address, value))
+}
+
+// Memory compares by content, with every cell past the end reading as zero. The length and the
+// limit are not part of it.
+impl PartialEq for Memory {
+  fn eq(&self, other: &Memory) -> bool {
+    let pages = |memory: &Memory| {
+      memory
+        .pages
+        .len()
+        .max(memory.base.len().div_ceil(PAGE_SIZE))
+    };
+    self.fingerprint == other.fingerprint
+      && (0..pages(self).max(pages(other))).all(|page| self.same_page(other, page))
+  }
+}
+
+impl Eq for Memory {}
+
+impl Hash for Memory {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.fingerprint.hash(state);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn untouched_cells_read_zero() {
+    let memory = Memory::new(&[1, 2, 3]);
+    assert_eq!(memory.get(2), 3);
+    assert_eq!(memory.get(3), 0);
+    assert_eq!(memory.get(usize::MAX), 0);
+  }
+
+  #[test]
+  fn grows_on_write() {
+    let mut memory = Memory::new(&[1, 2, 3]);
+    memory.set(1_000_000, 7).unwrap();
+    assert_eq!(memory.get(1_000_000), 7);
+    assert_eq!(memory.len(), 1_000_001);
+  }
+
+  #[test]
+  fn fingerprint() {
+    let mut memory = Memory::new(&[1, 2, 3]);
+    let initial = memory.fingerprint();
+    memory.set(1, 5).unwrap();
+    memory.set(2000, 7).unwrap();
+    assert_ne!(memory.fingerprint(), initial);
+    memory.set(2000, 0).unwrap();
+    memory.set(1, 2).unwrap();
+    assert_eq!(memory.fingerprint(), initial);
+    assert_ne!(Memory::new(&[2, 1, 3]).fingerprint(), initial);
+  }
+
+  #[test]
+  fn copy_on_write() {
+    static CODE: [i64; 3] = [1, 2, 3];
+    let mut memory = Memory::from_static(&CODE);
+    let fork = memory.clone();
+    memory.set(1, 5).unwrap();
+    memory.set(2000, 7).unwrap();
+    assert_eq!((memory.get(0), memory.get(1), memory.get(2000)), (1, 5, 7));
+    assert_eq!(fork.to_vec(), vec![1, 2, 3]);
+    assert!(memory != fork);
+
+    memory.set(1, 2).unwrap();
+    memory.set(2000, 0).unwrap();
+    assert!(memory == fork);
+    assert!(memory == Memory::new(&[1, 2, 3, 0]));
+    assert!(memory != Memory::new(&[1, 2, 4]));
+  }
+
+  #[test]
+  fn limit() {
+    let mut memory = Memory::new(&[1, 2, 3]);
+    memory.set_limit(10);
+    assert_eq!(memory.set(9, 1), Ok(()));
+    assert_eq!(
+      memory.set(10, 1),
+      Err(MemoryLimit {
+        address: 10,
+        limit: 10
+      })
+    );
+  }
+}
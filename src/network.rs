@@ -0,0 +1,271 @@
+use crate::computer::{IntcodeError, Program, State};
+use std::collections::VecDeque;
+use std::fmt;
+
+pub const NAT_ADDRESS: usize = 255;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Packet {
+  pub dest: usize,
+  pub x: i64,
+  pub y: i64,
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum NetworkEvent {
+  NatReceived(Packet),
+  NatWake(Packet),
+  Deadlock,
+  Halted,
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum NetworkError {
+  Machine { address: usize, error: IntcodeError },
+  UnknownAddress { from: Option<usize>, dest: i64 },
+  TooManyMachines { size: usize },
+}
+
+impl fmt::Display for NetworkError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      NetworkError::Machine { address, error } => write!(f, "machine {}: {}", address, error),
+      NetworkError::UnknownAddress {
+        from: Some(from),
+        dest,
+      } => write!(
+        f,
+        "machine {} sent a packet to unknown address {}",
+        from, dest
+      ),
+      NetworkError::UnknownAddress { from: None, dest } => {
+        write!(f, "packet sent to unknown address {}", dest)
+      }
+      NetworkError::TooManyMachines { size } => write!(
+        f,
+        "a network of {} machines reaches the NAT address {}",
+        size, NAT_ADDRESS
+      ),
+    }
+  }
+}
+
+impl std::error::Error for NetworkError {}
+
+pub struct Network {
+  machines: Vec<Program>,
+  queues: Vec<VecDeque<i64>>,
+  nat: Option<Packet>,
+  events: VecDeque<NetworkEvent>,
+}
+
+impl Network {
+  /// The NAT listens on address 255, so a network has at most 254 machines
+  pub fn new(code: &[i64], size: usize) -> Result<Self, NetworkError> {
+    if size >= NAT_ADDRESS {
+      return Err(NetworkError::TooManyMachines { size });
+    }
+    let prototype = Program::new(code.to_vec());
+    let machines = (0..size)
+      .map(|address| {
+        let mut machine = prototype.clone();
+        machine.push_input(address as i64);
+        machine
+      })
+      .collect();
+    Ok(Self {
+      machines,
+      queues: vec![VecDeque::new(); size],
+      nat: None,
+      events: VecDeque::new(),
+    })
+  }
+
+  pub fn machine(&self, address: usize) -> &Program {
+    &self.machines[address]
+  }
+
+  pub fn nat(&self) -> Option<Packet> {
+    self.nat
+  }
+
+  pub fn send(&mut self, packet: Packet) -> Result<(), NetworkError> {
+    if packet.dest != NAT_ADDRESS && packet.dest >= self.queues.len() {
+      return Err(NetworkError::UnknownAddress {
+        from: None,
+        dest: packet.dest as i64,
+      });
+    }
+    self.deliver(packet);
+    Ok(())
+  }
+
+  fn deliver(&mut self, packet: Packet) {
+    if packet.dest == NAT_ADDRESS {
+      self.nat = Some(packet);
+      self.events.push_back(NetworkEvent::NatReceived(packet));
+    } else {
+      self.queues[packet.dest].extend(&[packet.x, packet.y]);
+    }
+  }
+
+  // Gives every machine that has not halted one turn, in address order. A machine without
+  // pending packets reads -1 and runs until it asks for input again.
+  fn round(&mut self) -> Result<(), NetworkError> {
+    let mut idle = true;
+    for address in 0..self.machines.len() {
+      let machine = &mut self.machines[address];
+      if machine.is_halted() {
+        continue;
+      }
+      if self.queues[address].is_empty() {
+        machine.push_input(-1);
+      } else {
+        idle = false;
+        machine.extend_input(self.queues[address].drain(..));
+      }
+      let error = |error| NetworkError::Machine { address, error };
+      match machine.run_until_blocked().map_err(error)? {
+        State::NeedsInput | State::Halted => {}
+        state => return Err(error(machine.blocked(state))),
+      }
+
+      let packets: Vec<_> = machine
+        .output
+        .drain(..machine.output.len() / 3 * 3)
+        .collect();
+      for packet in packets.chunks(3) {
+        idle = false;
+        let dest = packet[0];
+        if dest != NAT_ADDRESS as i64 && !(0..self.queues.len() as i64).contains(&dest) {
+          return Err(NetworkError::UnknownAddress {
+            from: Some(address),
+            dest,
+          });
+        }
+        self.deliver(Packet {
+          dest: dest as usize,
+          x: packet[1],
+          y: packet[2],
+        });
+      }
+    }
+
+    if self.machines.iter().all(Program::is_halted) {
+      self.events.push_back(NetworkEvent::Halted);
+    } else if idle {
+      match self.nat {
+        Some(packet) => {
+          let packet = Packet { dest: 0, ..packet };
+          self.deliver(packet);
+          self.events.push_back(NetworkEvent::NatWake(packet));
+        }
+        None => self.events.push_back(NetworkEvent::Deadlock),
+      }
+    }
+    Ok(())
+  }
+
+  pub fn run(&mut self) -> Result<NetworkEvent, NetworkError> {
+    loop {
+      if let Some(event) = self.events.pop_front() {
+        return Ok(event);
+      }
+      self.round()?;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asm::assemble;
+
+  // Adds x to y and passes the packet on to the next machine, the last one sends it to the NAT.
+  const RELAY: &str = "
+          in addr
+    loop: in x
+          eq x, #-1, t
+          jt t, #loop
+          in y
+          add y, x, y
+          add addr, #1, dest
+          eq dest, #3, t
+          jf t, #send
+          add #255, #0, dest
+    send: out dest
+          out x
+          out y
+          jt #1, #loop
+    addr: .data 0
+    x:    .data 0
+    y:    .data 0
+    t:    .data 0
+    dest: .data 0
+  ";
+
+  #[test]
+  fn nat() {
+    let mut network = Network::new(&assemble(RELAY).unwrap(), 3).unwrap();
+    network
+      .send(Packet {
+        dest: 0,
+        x: 1,
+        y: 10,
+      })
+      .unwrap();
+    let nat = |x, y| Packet { dest: 255, x, y };
+    let wake = |x, y| Packet { dest: 0, x, y };
+    assert_eq!(network.run(), Ok(NetworkEvent::NatReceived(nat(1, 13))));
+    assert_eq!(network.run(), Ok(NetworkEvent::NatWake(wake(1, 13))));
+    assert_eq!(network.run(), Ok(NetworkEvent::NatReceived(nat(1, 16))));
+    assert_eq!(network.run(), Ok(NetworkEvent::NatWake(wake(1, 16))));
+    assert_eq!(network.nat(), Some(nat(1, 16)));
+  }
+
+  #[test]
+  fn deadlock_and_halt() {
+    let mut network = Network::new(&assemble(RELAY).unwrap(), 3).unwrap();
+    assert_eq!(network.run(), Ok(NetworkEvent::Deadlock));
+    assert_eq!(network.run(), Ok(NetworkEvent::Deadlock));
+
+    // in 5; hlt
+    let mut network = Network::new(&[3, 5, 99], 2).unwrap();
+    assert_eq!(network.run(), Ok(NetworkEvent::Halted));
+  }
+
+  #[test]
+  fn unknown_address() {
+    // in 9; out #7; out #1; out #2; hlt
+    let mut network = Network::new(&[3, 9, 104, 7, 104, 1, 104, 2, 99], 2).unwrap();
+    assert_eq!(
+      network.run(),
+      Err(NetworkError::UnknownAddress {
+        from: Some(0),
+        dest: 7
+      })
+    );
+
+    let mut network = Network::new(&[99], 2).unwrap();
+    let packet = Packet {
+      dest: 7,
+      x: 1,
+      y: 2,
+    };
+    assert_eq!(
+      network.send(packet),
+      Err(NetworkError::UnknownAddress {
+        from: None,
+        dest: 7
+      })
+    );
+  }
+  #[test]
+  fn size_limit() {
+    assert!(Network::new(&[99], NAT_ADDRESS - 1).is_ok());
+    assert_eq!(
+      Network::new(&[99], NAT_ADDRESS).err(),
+      Some(NetworkError::TooManyMachines { size: 255 })
+    );
+  }
+}
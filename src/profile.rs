@@ -0,0 +1,262 @@
+use crate::computer::{Event, Opcode};
+use crate::disasm::decode_memory;
+use crate::memory::Memory;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct Heat {
+  pub reads: u64,
+  pub writes: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Profile {
+  instructions: u64,
+  addresses: HashMap<usize, u64>,
+  opcodes: HashMap<&'static str, u64>,
+  modes: HashMap<Opcode, u64>,
+  cells: HashMap<usize, Heat>,
+  halted: bool,
+}
+
+fn sorted<K: Copy + Ord + Hash>(counts: &HashMap<K, u64>) -> Vec<(K, u64)> {
+  let mut counts: Vec<_> = counts.iter().map(|(&key, &count)| (key, count)).collect();
+  counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+  counts
+}
+
+pub(crate) fn percent(part: u64, total: u64) -> f64 {
+  part as f64 * 100.0 / total.max(1) as f64
+}
+
+fn signature(opcode: Opcode) -> String {
+  let operands: Vec<_> = opcode
+    .modes()
+    .iter()
+    .map(|mode| format!("{}_", mode.sigil()))
+    .collect();
+  format!("{} {}", opcode.mnemonic(), operands.join(", "))
+    .trim_end()
+    .to_owned()
+}
+
+impl Profile {
+  pub(crate) fn record(&mut self, memory: &Memory, relative_base: i64, event: &Event) {
+    self.record_instruction(event.ptr, event.opcode);
+    for address in event.read_addresses(memory, relative_base) {
+      self.cells.entry(address).or_default().reads += 1;
+    }
+    if let Some((address, _)) = event.write {
+      self.cells.entry(address).or_default().writes += 1;
+    }
+    self.halted = false;
+  }
+
+  // Halting does not produce an event, but the `hlt` did run. Running a halted program again
+  // reports the halt again, but it is only counted once.
+  pub(crate) fn record_halt(&mut self, ptr: usize) {
+    if !self.halted {
+      self.record_instruction(ptr, Opcode::Exit);
+      self.halted = true;
+    }
+  }
+
+  fn record_instruction(&mut self, ptr: usize, opcode: Opcode) {
+    self.instructions += 1;
+    *self.addresses.entry(ptr).or_default() += 1;
+    *self.opcodes.entry(opcode.mnemonic()).or_default() += 1;
+    *self.modes.entry(opcode).or_default() += 1;
+  }
+
+  pub fn instructions(&self) -> u64 {
+    self.instructions
+  }
+
+  pub fn executions(&self, address: usize) -> u64 {
+    self.addresses.get(&address).copied().unwrap_or_default()
+  }
+
+  pub fn opcode_count(&self, mnemonic: &str) -> u64 {
+    self.opcodes.get(mnemonic).copied().unwrap_or_default()
+  }
+
+  pub fn mode_count(&self, opcode: Opcode) -> u64 {
+    self.modes.get(&opcode).copied().unwrap_or_default()
+  }
+
+  pub fn heat(&self, address: usize) -> Heat {
+    self.cells.get(&address).copied().unwrap_or_default()
+  }
+
+  fn write_report(&self, out: &mut fmt::Formatter, memory: &Memory, top: usize) -> fmt::Result {
+    writeln!(out, "{} instructions executed", self.instructions)?;
+
+    writeln!(out, "\nhot instructions:")?;
+    for (address, count) in sorted(&self.addresses).into_iter().take(top) {
+      let listing = match decode_memory(memory, address) {
+        Some(item) => item.to_string(),
+        None => format!("{:>5}: .data {}", address, memory.get(address)),
+      };
+      writeln!(
+        out,
+        "{:>10} {:>6.2}% {}",
+        count,
+        percent(count, self.instructions),
+        listing
+      )?;
+    }
+
+    writeln!(out, "\nopcodes:")?;
+    for (mnemonic, count) in sorted(&self.opcodes).into_iter().take(top) {
+      writeln!(
+        out,
+        "{:>10} {:>6.2}% {}",
+        count,
+        percent(count, self.instructions),
+        mnemonic
+      )?;
+    }
+
+    writeln!(out, "\nparameter modes:")?;
+    let mut modes: Vec<_> = self
+      .modes
+      .iter()
+      .map(|(&opcode, &count)| (signature(opcode), count))
+      .collect();
+    modes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    for (signature, count) in modes.into_iter().take(top) {
+      writeln!(
+        out,
+        "{:>10} {:>6.2}% {}",
+        count,
+        percent(count, self.instructions),
+        signature
+      )?;
+    }
+
+    writeln!(out, "\nmemory heat:")?;
+    writeln!(out, "{:>10} {:>10} address", "reads", "writes")?;
+    let mut cells: Vec<_> = self.cells.iter().map(|(&a, &heat)| (a, heat)).collect();
+    cells.sort_by(|a, b| {
+      let total = |heat: Heat| heat.reads + heat.writes;
+      total(b.1).cmp(&total(a.1)).then(a.0.cmp(&b.0))
+    });
+    for (address, heat) in cells.into_iter().take(top) {
+      writeln!(out, "{:>10} {:>10} {}", heat.reads, heat.writes, address)?;
+    }
+    Ok(())
+  }
+
+  pub fn report<'a>(&'a self, memory: &'a Memory, top: usize) -> ProfileReport<'a> {
+    ProfileReport {
+      profile: self,
+      memory,
+      top,
+    }
+  }
+}
+
+pub struct ProfileReport<'a> {
+  profile: &'a Profile,
+  memory: &'a Memory,
+  top: usize,
+}
+
+impl fmt::Display for ProfileReport<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.profile.write_report(f, self.memory, self.top)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::computer::{Opcode, ParamMode, Program};
+
+  fn profiled(code: Vec<i64>) -> Program {
+    let mut program = Program::new(code);
+    program.set_profiling(true);
+    program.run_to_halt().unwrap();
+    program
+  }
+
+  #[test]
+  fn counts() {
+    // count to 16, printing the cells after the relative base
+    let program = profiled(vec![
+      109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ]);
+    let profile = program.profile().unwrap();
+    assert_eq!(profile.instructions(), 16 * 5 + 1);
+    assert_eq!(profile.executions(2), 16);
+    assert_eq!(profile.executions(15), 1);
+    assert_eq!(profile.opcode_count("hlt"), 1);
+    assert_eq!(profile.opcode_count("arb"), 16);
+    assert_eq!(
+      profile.mode_count(Opcode::JumpIfFalse([
+        ParamMode::Position,
+        ParamMode::Immediate
+      ])),
+      16
+    );
+    assert_eq!(profile.heat(100).reads, 32);
+    assert_eq!(profile.heat(100).writes, 16);
+    assert_eq!(profile.heat(101).reads, 16);
+    assert_eq!(profile.heat(0).reads, 1);
+  }
+
+  #[test]
+  fn self_modifying_operands() {
+    // add 1, 1, 1 overwrites its own first operand
+    let program = profiled(vec![1, 1, 1, 1, 99]);
+    let profile = program.profile().unwrap();
+    assert_eq!(profile.heat(1).reads, 2);
+    assert_eq!(profile.heat(1).writes, 1);
+    assert_eq!(profile.heat(2).reads, 0);
+  }
+
+  #[test]
+  fn halt_counted_once() {
+    let mut program = profiled(vec![99]);
+    program.run().unwrap();
+    program.step().unwrap();
+    let profile = program.profile().unwrap();
+    assert_eq!(profile.instructions(), 1);
+    assert_eq!(profile.executions(0), 1);
+  }
+
+  #[test]
+  fn report() {
+    let program = profiled(vec![1101, 2, 3, 9, 1002, 9, 2, 9, 99, 0]);
+    let report = program
+      .profile()
+      .unwrap()
+      .report(program.memory(), 3)
+      .to_string();
+    assert_eq!(
+      report,
+      "3 instructions executed
+
+hot instructions:
+         1  33.33%     0: add #2, #3, 9
+         1  33.33%     4: mul 9, #2, 9
+         1  33.33%     8: hlt
+
+opcodes:
+         1  33.33% add
+         1  33.33% hlt
+         1  33.33% mul
+
+parameter modes:
+         1  33.33% add #_, #_, _
+         1  33.33% hlt
+         1  33.33% mul _, #_, _
+
+memory heat:
+     reads     writes address
+         1          2 9
+"
+    );
+  }
+}
@@ -0,0 +1,316 @@
+//! Recorded Intcode sessions, one line per input consumed or output produced:
+//!
+//! ```text
+//! intcode-replay 1
+//! in 1 5
+//! out 3 10
+//! halt 3
+//! end
+//! ```
+//!
+//! The number after the kind counts the instructions executed up to and including the one that
+//! did the I/O, so a replay also notices when the same values are produced along another path.
+
+use crate::computer::{Event, IntcodeError, Opcode, Program, State};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+const MAGIC: &str = "intcode-replay 1";
+
+#[derive(Debug)]
+pub enum ReplayError {
+  Io(io::Error),
+  Parse { line: usize, message: String },
+}
+
+impl fmt::Display for ReplayError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ReplayError::Io(err) => write!(f, "{}", err),
+      ReplayError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+    }
+  }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+  fn from(err: io::Error) -> Self {
+    ReplayError::Io(err)
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Io {
+  Input(i64),
+  Output(i64),
+  Halt,
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Entry {
+  pub instructions: u64,
+  pub io: Io,
+}
+
+impl fmt::Display for Entry {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.io {
+      Io::Input(value) => write!(f, "input {}", value)?,
+      Io::Output(value) => write!(f, "output {}", value)?,
+      Io::Halt => write!(f, "halt")?,
+    }
+    write!(f, " after {} instructions", self.instructions)
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct Recording {
+  instructions: u64,
+  entries: Vec<Entry>,
+}
+
+impl Recording {
+  pub(crate) fn record(&mut self, event: &Event) {
+    self.instructions += 1;
+    let io = match (event.opcode, event.write) {
+      (Opcode::Input(_), Some((_, value))) => Io::Input(value),
+      (Opcode::Output(_), _) => Io::Output(event.reads()[0]),
+      _ => return,
+    };
+    self.entries.push(Entry {
+      instructions: self.instructions,
+      io,
+    });
+  }
+
+  // Running a halted program again reports the halt again, but it is only recorded once.
+  pub(crate) fn record_halt(&mut self) {
+    if self.entries.last().map(|entry| entry.io) != Some(Io::Halt) {
+      self.entries.push(Entry {
+        instructions: self.instructions,
+        io: Io::Halt,
+      });
+    }
+  }
+
+  pub fn instructions(&self) -> u64 {
+    self.instructions
+  }
+
+  pub fn entries(&self) -> &[Entry] {
+    &self.entries
+  }
+
+  pub fn inputs(&self) -> impl Iterator<Item = i64> + '_ {
+    self.entries.iter().filter_map(|entry| match entry.io {
+      Io::Input(value) => Some(value),
+      _ => None,
+    })
+  }
+
+  pub fn outputs(&self) -> impl Iterator<Item = i64> + '_ {
+    self.entries.iter().filter_map(|entry| match entry.io {
+      Io::Output(value) => Some(value),
+      _ => None,
+    })
+  }
+
+  pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+    writeln!(w, "{}", MAGIC)?;
+    for entry in &self.entries {
+      match entry.io {
+        Io::Input(value) => writeln!(w, "in {} {}", entry.instructions, value)?,
+        Io::Output(value) => writeln!(w, "out {} {}", entry.instructions, value)?,
+        Io::Halt => writeln!(w, "halt {}", entry.instructions)?,
+      }
+    }
+    writeln!(w, "end")
+  }
+
+  pub fn load(r: impl BufRead) -> Result<Recording, ReplayError> {
+    let error = |line: usize, message: &str| ReplayError::Parse {
+      line,
+      message: message.to_owned(),
+    };
+    let mut recording = Recording::default();
+    let mut lines = r.lines().enumerate();
+    let magic = match lines.next() {
+      Some((_, line)) => line?,
+      None => String::new(),
+    };
+    if magic != MAGIC {
+      return Err(error(1, &format!("expected `{}`", MAGIC)));
+    }
+    for (idx, line) in lines {
+      let (line, text) = (idx + 1, line?);
+      if text == "end" {
+        return Ok(recording);
+      }
+      let fields: Vec<_> = text.split(' ').collect();
+      let number = |idx: usize| -> Result<i64, ReplayError> {
+        fields[idx]
+          .parse()
+          .map_err(|_| error(line, "invalid number"))
+      };
+      let io = match (fields[0], fields.len()) {
+        ("in", 3) => Io::Input(number(2)?),
+        ("out", 3) => Io::Output(number(2)?),
+        ("halt", 2) => Io::Halt,
+        _ => return Err(error(line, "expected `in`, `out` or `halt` entry")),
+      };
+      let instructions: u64 = fields[1]
+        .parse()
+        .map_err(|_| error(line, "invalid instruction count"))?;
+      if instructions < recording.instructions {
+        return Err(error(line, "instruction count goes backwards"));
+      }
+      recording.instructions = instructions;
+      recording.entries.push(Entry { instructions, io });
+    }
+    Err(error(0, "unexpected end of replay"))
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Divergence {
+  pub index: usize,
+  pub expected: Option<Entry>,
+  pub actual: Option<Entry>,
+}
+
+impl fmt::Display for Divergence {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let describe = |entry: Option<Entry>| match entry {
+      Some(entry) => entry.to_string(),
+      None => "end of session".to_owned(),
+    };
+    write!(
+      f,
+      "entry {}: expected {}, got {}",
+      self.index + 1,
+      describe(self.expected),
+      describe(self.actual)
+    )
+  }
+}
+
+/// Feeds the recorded inputs to `program` whenever it asks for one and compares everything it
+/// does with the recording. The session ends when the program halts or wants more input than
+/// was recorded.
+pub fn replay(
+  mut program: Program,
+  recording: &Recording,
+) -> Result<Option<Divergence>, IntcodeError> {
+  program.set_recording(true);
+  let mut inputs = recording.inputs();
+  let mut checked = 0;
+  loop {
+    let state = program.run()?;
+    let waiting = match state {
+      State::NeedsInput => match inputs.next() {
+        Some(value) => {
+          program.push_input(value);
+          false
+        }
+        None => true,
+      },
+      _ => false,
+    };
+
+    let actual = program.recording().map_or(&[][..], Recording::entries);
+    for (index, &entry) in actual.iter().enumerate().skip(checked) {
+      if recording.entries.get(index) != Some(&entry) {
+        return Ok(Some(Divergence {
+          index,
+          expected: recording.entries.get(index).copied(),
+          actual: Some(entry),
+        }));
+      }
+    }
+    checked = actual.len();
+
+    match state {
+      State::Output(_) => {}
+      State::NeedsInput if !waiting => {}
+      State::NeedsInput | State::Halted => break,
+      state => return Err(program.blocked(state)),
+    }
+  }
+  Ok(recording.entries.get(checked).map(|&expected| Divergence {
+    index: checked,
+    expected: Some(expected),
+    actual: None,
+  }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fixtures::DOUBLER;
+
+  fn record(code: &[i64], input: &[i64]) -> Recording {
+    let mut program = Program::new(code.to_vec());
+    program.set_recording(true);
+    program.extend_input(input.iter().copied());
+    program.run_until_blocked().unwrap();
+    program.recording().unwrap().clone()
+  }
+
+  #[test]
+  fn round_trip() {
+    let recording = record(&[3, 9, 104, 7, 4, 9, 99], &[5]);
+    let mut buffer = Vec::new();
+    recording.save(&mut buffer).unwrap();
+    assert_eq!(
+      String::from_utf8(buffer.clone()).unwrap(),
+      "intcode-replay 1\nin 1 5\nout 2 7\nout 3 5\nhalt 3\nend\n"
+    );
+    assert_eq!(Recording::load(&buffer[..]).unwrap(), recording);
+    assert_eq!(recording.outputs().collect::<Vec<_>>(), vec![7, 5]);
+
+    let error = Recording::load(&b"intcode-replay 1\nin 2 x\nend\n"[..]).unwrap_err();
+    assert_eq!(error.to_string(), "line 2: invalid number");
+  }
+
+  #[test]
+  fn malformed() {
+    let load = |text: &str| Recording::load(text.as_bytes()).unwrap_err().to_string();
+    assert_eq!(
+      load("intcode-replay 1\nin -1 5\nend\n"),
+      "line 2: invalid instruction count"
+    );
+    assert_eq!(
+      load("intcode-replay 1\nout 3 1\nhalt 2\nend\n"),
+      "line 3: instruction count goes backwards"
+    );
+  }
+
+  #[test]
+  fn matching_replay() {
+    let recording = record(&DOUBLER, &[1, 2, 3]);
+    assert_eq!(recording.entries().len(), 6);
+    assert_eq!(replay(Program::new(DOUBLER), &recording), Ok(None));
+  }
+
+  #[test]
+  fn first_divergence() {
+    let recording = record(&DOUBLER, &[1, 2, 3]);
+    // mul 11, #3, 11 instead of #2
+    let mut tripler = DOUBLER;
+    tripler[4] = 3;
+    let divergence = replay(Program::new(tripler), &recording).unwrap().unwrap();
+    assert_eq!(divergence.index, 1);
+    assert_eq!(
+      divergence.to_string(),
+      "entry 2: expected output 2 after 3 instructions, got output 3 after 3 instructions"
+    );
+
+    // in 9; mul 9, #2, 9; out 9; hlt
+    let divergence = replay(Program::new([3, 9, 1002, 9, 2, 9, 4, 9, 99, 0]), &recording);
+    assert_eq!(
+      divergence.unwrap().unwrap().to_string(),
+      "entry 3: expected input 2 after 5 instructions, got halt after 3 instructions"
+    );
+  }
+}
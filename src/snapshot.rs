@@ -0,0 +1,283 @@
+//! Plain-text snapshots of a [`Program`], one `key value` pair per line:
+//!
+//! ```text
+//! intcode-snapshot 1
+//! ptr 12
+//! relative_base 2000
+//! halted 0
+//! input 5,-1
+//! output 0,0,3
+//! memory 1024 16777216
+//! 0 1002,4,3,4,33
+//! 1000 7
+//! end
+//! ```
+//!
+//! `input` and `output` are the pending queues. `memory` gives the memory length and limit and
+//! is followed by one line per run of non-zero cells: the start address, then the values. Every
+//! cell that is not listed is zero. `halted` must agree with the instruction at `ptr`.
+
+use crate::computer::Program;
+use crate::memory::{Memory, DEFAULT_MEMORY_LIMIT};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+const MAGIC: &str = "intcode-snapshot 1";
+const RUN_LENGTH: usize = 32;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+  Io(io::Error),
+  Parse { line: usize, message: String },
+}
+
+impl fmt::Display for SnapshotError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SnapshotError::Io(err) => write!(f, "{}", err),
+      SnapshotError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+    }
+  }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+  fn from(err: io::Error) -> Self {
+    SnapshotError::Io(err)
+  }
+}
+
+fn join<'a>(values: impl IntoIterator<Item = &'a i64>) -> String {
+  let values: Vec<_> = values.into_iter().map(i64::to_string).collect();
+  values.join(",")
+}
+
+fn write_list<'a>(
+  w: &mut impl Write,
+  key: &str,
+  values: impl IntoIterator<Item = &'a i64>,
+) -> io::Result<()> {
+  match join(values) {
+    values if values.is_empty() => writeln!(w, "{}", key),
+    values => writeln!(w, "{} {}", key, values),
+  }
+}
+
+fn field(key: &str, (line, text): (usize, String)) -> Result<(usize, String), SnapshotError> {
+  match text.strip_prefix(key) {
+    Some(rest) if rest.is_empty() || rest.starts_with(' ') => Ok((line, rest.trim().to_owned())),
+    _ => Err(SnapshotError::Parse {
+      line,
+      message: format!("expected `{}`", key),
+    }),
+  }
+}
+
+impl Program {
+  pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+    writeln!(w, "{}", MAGIC)?;
+    writeln!(w, "ptr {}", self.ptr)?;
+    writeln!(w, "relative_base {}", self.relative_base)?;
+    writeln!(w, "halted {}", self.is_halted() as u8)?;
+    write_list(&mut w, "input", &self.input)?;
+    write_list(&mut w, "output", &self.output)?;
+    writeln!(w, "memory {} {}", self.memory.len(), self.memory.limit())?;
+
+    let mut address = 0;
+    while address < self.memory.len() {
+      if self.memory.get(address) == 0 {
+        address += 1;
+        continue;
+      }
+      let run: Vec<_> = (address..self.memory.len().min(address + RUN_LENGTH))
+        .map(|a| self.memory.get(a))
+        .take_while(|&value| value != 0)
+        .collect();
+      writeln!(w, "{} {}", address, join(&run))?;
+      address += run.len();
+    }
+    writeln!(w, "end")
+  }
+
+  pub fn load(r: impl BufRead) -> Result<Program, SnapshotError> {
+    let mut lines = r.lines().enumerate();
+    let mut next = || -> Result<(usize, String), SnapshotError> {
+      match lines.next() {
+        Some((idx, line)) => Ok((idx + 1, line?)),
+        None => Err(SnapshotError::Parse {
+          line: 0,
+          message: "unexpected end of snapshot".to_owned(),
+        }),
+      }
+    };
+    let error = |line: usize, message: &str| SnapshotError::Parse {
+      line,
+      message: message.to_owned(),
+    };
+    let number = |line: usize, s: &str| -> Result<i64, SnapshotError> {
+      s.parse().map_err(|_| error(line, "invalid number"))
+    };
+    let size = |line: usize, s: &str| -> Result<usize, SnapshotError> {
+      match number(line, s)? {
+        n if n >= 0 => Ok(n as usize),
+        _ => Err(error(line, "expected a non-negative number")),
+      }
+    };
+    let list = |line: usize, s: &str| -> Result<Vec<i64>, SnapshotError> {
+      s.split(',')
+        .filter(|v| !v.is_empty())
+        .map(|v| number(line, v))
+        .collect()
+    };
+
+    field(MAGIC, next()?)?;
+    let (line, ptr) = field("ptr", next()?)?;
+    let ptr = size(line, &ptr)?;
+    let (line, relative_base) = field("relative_base", next()?)?;
+    let relative_base = number(line, &relative_base)?;
+    let (halted_line, halted) = field("halted", next()?)?;
+    let halted = number(halted_line, &halted)? != 0;
+    let (line, input) = field("input", next()?)?;
+    let input = list(line, &input)?;
+    let (line, output) = field("output", next()?)?;
+    let output = list(line, &output)?;
+    let (line, header) = field("memory", next()?)?;
+    let header = header
+      .split(' ')
+      .map(|v| size(line, v))
+      .collect::<Result<Vec<_>, _>>()?;
+    let (len, limit) = match header[..] {
+      [len, limit] => (len, limit),
+      _ => return Err(error(line, "expected memory length and limit")),
+    };
+    // Snapshots get passed around as reproducers, so they must not make us allocate at will
+    if limit > DEFAULT_MEMORY_LIMIT {
+      return Err(error(line, "memory limit too large"));
+    }
+    if len > limit {
+      return Err(error(line, "memory length exceeds limit"));
+    }
+
+    let mut memory = Memory::new(&[]);
+    memory.set_limit(limit);
+    loop {
+      let (line, run) = next()?;
+      if run == "end" {
+        break;
+      }
+      let (address, values) = run
+        .split_once(' ')
+        .ok_or_else(|| error(line, "expected `<address> <values>`"))?;
+      let address = size(line, address)?;
+      if address >= limit {
+        return Err(error(line, "cell exceeds memory limit"));
+      }
+      for (offset, value) in list(line, values)?.into_iter().enumerate() {
+        memory
+          .set(address + offset, value)
+          .map_err(|_| error(line, "cell exceeds memory limit"))?;
+      }
+    }
+    memory.len = memory.len.max(len);
+
+    let mut program = Program::new(Vec::new());
+    program.memory = memory;
+    program.ptr = ptr;
+    program.relative_base = relative_base;
+    program.input = input.into();
+    program.output = output;
+    if program.is_halted() != halted {
+      return Err(error(
+        halted_line,
+        "halt status does not match instruction at ptr",
+      ));
+    }
+    Ok(program)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::computer::State;
+
+  fn round_trip(program: &Program) -> (String, Program) {
+    let mut buffer = Vec::new();
+    program.save(&mut buffer).unwrap();
+    let loaded = Program::load(&buffer[..]).unwrap();
+    (String::from_utf8(buffer).unwrap(), loaded)
+  }
+
+  #[test]
+  fn format() {
+    let mut program = Program::new(vec![3, 0, 109, 2000, 21101, 3, 4, 0, 104, 7, 99]);
+    program.extend_input(vec![5, -1]);
+    program.run().unwrap();
+    let (text, _) = round_trip(&program);
+    assert_eq!(
+      text,
+      "intcode-snapshot 1\nptr 10\nrelative_base 2000\nhalted 1\ninput -1\noutput\n\
+       memory 2001 16777216\n0 5\n2 109,2000,21101,3,4\n8 104,7,99\n2000 7\nend\n"
+    );
+  }
+
+  #[test]
+  fn resumes() {
+    let code = vec![
+      109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let mut program = Program::new(code.clone());
+    let first = program.run_until_outputs(5).unwrap().unwrap();
+    let (_, mut loaded) = round_trip(&program);
+    assert_eq!(loaded.ptr(), program.ptr());
+    assert_eq!(loaded.relative_base(), program.relative_base());
+    assert_eq!(loaded.memory().to_vec(), program.memory().to_vec());
+
+    let mut rest = loaded.run_to_halt().unwrap();
+    assert_eq!(rest, program.run_to_halt().unwrap());
+    let mut all = first;
+    all.append(&mut rest);
+    assert_eq!(all, code);
+  }
+
+  #[test]
+  fn pending_input() {
+    let mut program = Program::new(vec![3, 0, 4, 0, 3, 0, 4, 0, 99]);
+    program.extend_input(vec![1, 2]);
+    assert_eq!(program.run(), Ok(State::Output(1)));
+    let (_, mut loaded) = round_trip(&program);
+    assert_eq!(loaded.run(), Ok(State::Output(2)));
+    assert_eq!(loaded.run(), Ok(State::Halted));
+  }
+
+  #[test]
+  fn errors() {
+    let load = |text: &str| match Program::load(text.as_bytes()) {
+      Err(SnapshotError::Parse { line, .. }) => line,
+      _ => panic!("expected parse error"),
+    };
+    assert_eq!(load("intcode-snapshot 2\n"), 1);
+    assert_eq!(load("intcode-snapshot 1\nptr x\n"), 2);
+    assert_eq!(
+      load(
+        "intcode-snapshot 1\nptr 0\nrelative_base 0\nhalted 1\ninput\noutput\nmemory 1 10\n0 1\nend\n"
+      ),
+      4
+    );
+
+    let header = "intcode-snapshot 1\nptr 0\nrelative_base 0\nhalted 0\ninput\noutput\n";
+    assert_eq!(load(&format!("{}memory 1 -1\n", header)), 7);
+    assert_eq!(load(&format!("{}memory 1 {}\n", header, usize::MAX)), 7);
+    assert_eq!(load(&format!("{}memory 20 10\n", header)), 7);
+    assert_eq!(load(&format!("{}memory 1 10\n-3 1\nend\n", header)), 8);
+    assert_eq!(
+      load(&format!(
+        "{}memory 1 10\n4611686018427387904 1\nend\n",
+        header
+      )),
+      8
+    );
+    assert_eq!(load("intcode-snapshot 1\nptr -1\n"), 2);
+  }
+}
@@ -0,0 +1,430 @@
+use crate::computer::{Opcode, ParamMode};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+const STEP_LIMIT: usize = 1_000_000;
+
+/// A constant plus a weighted sum of variables, each variable named by the address of the memory
+/// cell it was read from at the start.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Linear {
+  constant: i64,
+  terms: BTreeMap<usize, i64>,
+}
+
+impl Linear {
+  pub fn constant(value: i64) -> Self {
+    Self {
+      constant: value,
+      terms: BTreeMap::new(),
+    }
+  }
+
+  pub fn variable(address: usize) -> Self {
+    Self {
+      constant: 0,
+      terms: std::iter::once((address, 1)).collect(),
+    }
+  }
+
+  pub fn as_constant(&self) -> Option<i64> {
+    if self.terms.is_empty() {
+      Some(self.constant)
+    } else {
+      None
+    }
+  }
+
+  pub fn constant_term(&self) -> i64 {
+    self.constant
+  }
+
+  pub fn coefficient(&self, variable: usize) -> i64 {
+    self.terms.get(&variable).copied().unwrap_or(0)
+  }
+
+  pub fn variables(&self) -> impl Iterator<Item = usize> + '_ {
+    self.terms.keys().copied()
+  }
+
+  fn add(&self, other: &Linear) -> Option<Linear> {
+    let mut sum = self.clone();
+    sum.constant = sum.constant.checked_add(other.constant)?;
+    for (&variable, &coefficient) in &other.terms {
+      let term = sum.terms.entry(variable).or_insert(0);
+      *term = term.checked_add(coefficient)?;
+      if *term == 0 {
+        sum.terms.remove(&variable);
+      }
+    }
+    Some(sum)
+  }
+
+  fn scale(&self, factor: i64) -> Option<Linear> {
+    if factor == 0 {
+      return Some(Linear::constant(0));
+    }
+    Some(Linear {
+      constant: self.constant.checked_mul(factor)?,
+      terms: self
+        .terms
+        .iter()
+        .map(|(&variable, &coefficient)| Some((variable, coefficient.checked_mul(factor)?)))
+        .collect::<Option<_>>()?,
+    })
+  }
+
+  fn mul(&self, other: &Linear) -> Option<Linear> {
+    match (self.as_constant(), other.as_constant()) {
+      (Some(factor), _) => other.scale(factor),
+      (_, Some(factor)) => self.scale(factor),
+      _ => None,
+    }
+  }
+
+  fn compare(&self, other: &Linear, test: fn(i64) -> bool) -> Option<Linear> {
+    let difference = self.add(&other.scale(-1)?)?.as_constant()?;
+    Some(Linear::constant(test(difference) as i64))
+  }
+
+  pub fn eval(&self, value: impl Fn(usize) -> i64) -> Option<i64> {
+    self
+      .terms
+      .iter()
+      .try_fold(self.constant, |sum, (&variable, &coefficient)| {
+        sum.checked_add(coefficient.checked_mul(value(variable))?)
+      })
+  }
+
+  /// Returns the first assignment, in the order of `ranges`, that makes the expression equal
+  /// `target`. Every variable but the last is enumerated and the last one is solved for.
+  pub fn solve(&self, target: i64, ranges: &[(usize, RangeInclusive<i64>)]) -> Option<Vec<i64>> {
+    if self
+      .variables()
+      .any(|variable| ranges.iter().all(|(v, _)| *v != variable))
+    {
+      return None;
+    }
+    let mut values = Vec::with_capacity(ranges.len());
+    let remainder = target as i128 - self.constant as i128;
+    if self.solve_from(remainder, ranges, &mut values) {
+      Some(values)
+    } else {
+      None
+    }
+  }
+
+  fn solve_from(
+    &self,
+    remainder: i128,
+    ranges: &[(usize, RangeInclusive<i64>)],
+    values: &mut Vec<i64>,
+  ) -> bool {
+    let ((variable, range), rest) = match ranges.split_first() {
+      Some(first) => first,
+      None => return remainder == 0,
+    };
+    let coefficient = self.coefficient(*variable) as i128;
+    if rest.is_empty() {
+      let value = match coefficient {
+        0 if remainder == 0 => *range.start() as i128,
+        0 => return false,
+        c if remainder % c == 0 => remainder / c,
+        _ => return false,
+      };
+      if !(*range.start() as i128..=*range.end() as i128).contains(&value) {
+        return false;
+      }
+      values.push(value as i64);
+      return true;
+    }
+    for value in range.clone() {
+      values.push(value);
+      if self.solve_from(remainder - coefficient * value as i128, rest, values) {
+        return true;
+      }
+      values.pop();
+    }
+    false
+  }
+}
+
+impl fmt::Display for Linear {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, (&variable, &coefficient)) in self.terms.iter().enumerate() {
+      let sign = match (i, coefficient < 0) {
+        (0, true) => "-",
+        (0, false) => "",
+        (_, true) => " - ",
+        (_, false) => " + ",
+      };
+      match coefficient.unsigned_abs() {
+        1 => write!(f, "{}[{}]", sign, variable)?,
+        c => write!(f, "{}{}*[{}]", sign, c, variable)?,
+      }
+    }
+    match self.constant {
+      constant if self.terms.is_empty() => write!(f, "{}", constant),
+      0 => Ok(()),
+      constant if constant < 0 => write!(f, " - {}", constant.unsigned_abs()),
+      constant => write!(f, " + {}", constant),
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SymbolicError {
+  InvalidInstruction { ptr: usize, instruction: i64 },
+  InvalidAddress { ptr: usize },
+  SymbolicInstruction { ptr: usize },
+  SymbolicAddress { ptr: usize },
+  SymbolicBranch { ptr: usize },
+  Input { ptr: usize },
+  StepLimit { ptr: usize },
+  Unknown { address: usize },
+}
+
+impl fmt::Display for SymbolicError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      SymbolicError::InvalidInstruction { ptr, instruction } => {
+        write!(f, "invalid instruction {} at {}", instruction, ptr)
+      }
+      SymbolicError::InvalidAddress { ptr } => write!(f, "invalid address at {}", ptr),
+      SymbolicError::SymbolicInstruction { ptr } => {
+        write!(f, "instruction at {} depends on the variables", ptr)
+      }
+      SymbolicError::SymbolicAddress { ptr } => {
+        write!(f, "address used at {} depends on the variables", ptr)
+      }
+      SymbolicError::SymbolicBranch { ptr } => {
+        write!(f, "branch at {} depends on the variables", ptr)
+      }
+      SymbolicError::Input { ptr } => write!(f, "input requested at {}", ptr),
+      SymbolicError::StepLimit { ptr } => write!(f, "step limit reached at {}", ptr),
+      SymbolicError::Unknown { address } => {
+        write!(
+          f,
+          "cell {} is not a linear function of the variables",
+          address
+        )
+      }
+    }
+  }
+}
+
+impl std::error::Error for SymbolicError {}
+
+/// Runs a program with some memory cells treated as unknowns. Values that cannot be tracked as a
+/// linear function of them (reads through a symbolic address, products of two variables) become
+/// `None`, which is only an error once something depends on it.
+pub struct Symbolic {
+  memory: BTreeMap<usize, Option<Linear>>,
+  ptr: usize,
+  relative_base: i64,
+  output: Vec<Option<Linear>>,
+}
+
+impl Symbolic {
+  pub fn new(code: &[i64], variables: &[usize]) -> Self {
+    let mut memory: BTreeMap<_, _> = code
+      .iter()
+      .enumerate()
+      .map(|(address, &value)| (address, Some(Linear::constant(value))))
+      .collect();
+    for &variable in variables {
+      memory.insert(variable, Some(Linear::variable(variable)));
+    }
+    Self {
+      memory,
+      ptr: 0,
+      relative_base: 0,
+      output: Vec::new(),
+    }
+  }
+
+  pub fn ptr(&self) -> usize {
+    self.ptr
+  }
+
+  pub fn output(&self) -> &[Option<Linear>] {
+    &self.output
+  }
+
+  pub fn cell(&self, address: usize) -> Result<Linear, SymbolicError> {
+    self.get(address).ok_or(SymbolicError::Unknown { address })
+  }
+
+  fn get(&self, address: usize) -> Option<Linear> {
+    match self.memory.get(&address) {
+      Some(value) => value.clone(),
+      None => Some(Linear::constant(0)),
+    }
+  }
+
+  fn concrete(&self, value: Option<Linear>) -> Option<i64> {
+    value?.as_constant()
+  }
+
+  fn address(&self, mode: ParamMode, idx: usize) -> Result<Option<usize>, SymbolicError> {
+    let operand = self.concrete(self.get(self.ptr + 1 + idx));
+    let address = match mode {
+      ParamMode::Relative => operand.and_then(|operand| operand.checked_add(self.relative_base)),
+      _ => operand,
+    };
+    match address {
+      Some(address) if address < 0 => Err(SymbolicError::InvalidAddress { ptr: self.ptr }),
+      address => Ok(address.map(|address| address as usize)),
+    }
+  }
+
+  fn read(&self, modes: &[ParamMode], idx: usize) -> Result<Option<Linear>, SymbolicError> {
+    match modes[idx] {
+      ParamMode::Immediate => Ok(self.get(self.ptr + 1 + idx)),
+      mode => Ok(
+        self
+          .address(mode, idx)?
+          .and_then(|address| self.get(address)),
+      ),
+    }
+  }
+
+  fn write(
+    &mut self,
+    modes: &[ParamMode],
+    idx: usize,
+    value: Option<Linear>,
+  ) -> Result<(), SymbolicError> {
+    let address = match modes[idx] {
+      ParamMode::Immediate => unreachable!("decoding rejects immediate writes"),
+      mode => self.address(mode, idx)?,
+    };
+    let address = address.ok_or(SymbolicError::SymbolicAddress { ptr: self.ptr })?;
+    self.memory.insert(address, value);
+    Ok(())
+  }
+
+  /// Executes one instruction and returns false once the program halts.
+  pub fn step(&mut self) -> Result<bool, SymbolicError> {
+    let ptr = self.ptr;
+    let instruction = self
+      .concrete(self.get(ptr))
+      .ok_or(SymbolicError::SymbolicInstruction { ptr })?;
+    let invalid = SymbolicError::InvalidInstruction { ptr, instruction };
+    let opcode = Opcode::try_from(instruction).map_err(|_| invalid)?;
+    let modes = opcode.modes();
+    if opcode.writes() && modes.last() == Some(&ParamMode::Immediate) {
+      return Err(invalid);
+    }
+
+    let mut next = ptr + opcode.size();
+    match opcode {
+      Opcode::Add(_) | Opcode::Mul(_) | Opcode::LessThan(_) | Opcode::Equals(_) => {
+        let value = match (self.read(modes, 0)?, self.read(modes, 1)?) {
+          (Some(a), Some(b)) => match opcode {
+            Opcode::Add(_) => a.add(&b),
+            Opcode::Mul(_) => a.mul(&b),
+            Opcode::LessThan(_) => a.compare(&b, |difference| difference < 0),
+            _ => a.compare(&b, |difference| difference == 0),
+          },
+          _ => None,
+        };
+        self.write(modes, 2, value)?;
+      }
+      Opcode::Input(_) => return Err(SymbolicError::Input { ptr }),
+      Opcode::Output(_) => {
+        let value = self.read(modes, 0)?;
+        self.output.push(value);
+      }
+      Opcode::JumpIfTrue(_) | Opcode::JumpIfFalse(_) => {
+        let condition = self
+          .concrete(self.read(modes, 0)?)
+          .ok_or(SymbolicError::SymbolicBranch { ptr })?;
+        if (condition != 0) == matches!(opcode, Opcode::JumpIfTrue(_)) {
+          let target = self
+            .concrete(self.read(modes, 1)?)
+            .ok_or(SymbolicError::SymbolicAddress { ptr })?;
+          next = usize::try_from(target).map_err(|_| SymbolicError::InvalidAddress { ptr })?;
+        }
+      }
+      Opcode::RelativeBase(_) => {
+        let offset = self
+          .concrete(self.read(modes, 0)?)
+          .ok_or(SymbolicError::SymbolicAddress { ptr })?;
+        self.relative_base = self
+          .relative_base
+          .checked_add(offset)
+          .ok_or(SymbolicError::InvalidAddress { ptr })?;
+      }
+      Opcode::Exit => return Ok(false),
+    }
+    self.ptr = next;
+    Ok(true)
+  }
+
+  pub fn run(&mut self) -> Result<(), SymbolicError> {
+    for _ in 0..STEP_LIMIT {
+      if !self.step()? {
+        return Ok(());
+      }
+    }
+    Err(SymbolicError::StepLimit { ptr: self.ptr })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn linear_output() {
+    // mul 9, #3, 11; add 11, 10, 11; hlt; a, b, t
+    let mut symbolic = Symbolic::new(&[1002, 9, 3, 11, 1, 11, 10, 11, 99, 0, 0, 0], &[9, 10]);
+    symbolic.run().unwrap();
+    let result = symbolic.cell(11).unwrap();
+    assert_eq!(result.to_string(), "3*[9] + [10]");
+    assert_eq!(result.eval(|variable| variable as i64), Some(37));
+    assert_eq!(
+      result.solve(20, &[(9, 0..=10), (10, 0..=10)]),
+      Some(vec![4, 8])
+    );
+    assert_eq!(result.solve(50, &[(9, 0..=10), (10, 0..=10)]), None);
+    assert_eq!(result.solve(20, &[(9, 0..=10)]), None);
+  }
+
+  #[test]
+  fn unknown_values() {
+    // add x, y, 9 reads through the variables in its operands, mul 1, 2, 10 multiplies them; hlt
+    let code = [1, 0, 0, 9, 2, 1, 2, 10, 99, 0, 0];
+    let mut symbolic = Symbolic::new(&code, &[1, 2]);
+    symbolic.run().unwrap();
+    assert_eq!(symbolic.cell(9), Err(SymbolicError::Unknown { address: 9 }));
+    assert_eq!(
+      symbolic.cell(10).unwrap_err().to_string(),
+      "cell 10 is not a linear function of the variables"
+    );
+    assert_eq!(symbolic.cell(1), Ok(Linear::variable(1)));
+  }
+
+  #[test]
+  fn symbolic_control_flow() {
+    // jt 3, #0; hlt
+    let mut symbolic = Symbolic::new(&[1005, 3, 0, 0, 99], &[3]);
+    assert_eq!(
+      symbolic.run(),
+      Err(SymbolicError::SymbolicBranch { ptr: 0 })
+    );
+    // eq 8, 8, 9; jt 9, #7; hlt; x, t
+    let mut symbolic = Symbolic::new(&[8, 8, 8, 9, 1005, 9, 7, 99, 0, 0], &[8]);
+    assert_eq!(symbolic.run(), Ok(()));
+    assert_eq!(symbolic.cell(9), Ok(Linear::constant(1)));
+    // add #1, #0, x with the destination operand as the variable
+    let mut symbolic = Symbolic::new(&[1101, 1, 0, 0, 99], &[3]);
+    assert_eq!(
+      symbolic.run(),
+      Err(SymbolicError::SymbolicAddress { ptr: 0 })
+    );
+  }
+}
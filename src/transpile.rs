@@ -0,0 +1,279 @@
+use crate::computer::{Opcode, ParamMode};
+use crate::disasm::{decode_at, Item};
+use std::collections::BTreeMap;
+use std::fmt;
+
+const VALUES_PER_LINE: usize = 16;
+
+const RUNTIME: &str = "\
+pub fn run(program: &mut Program) -> Result<State, IntcodeError> {
+  loop {
+    let (mut ptr, mut rb) = (program.ptr(), program.relative_base());
+    let state = execute(program, &mut ptr, &mut rb);
+    program.set_ptr(ptr);
+    program.set_relative_base(rb);
+    if let Some(state) = state {
+      return Ok(state);
+    }
+    match program.step()? {
+      Step::Executed(event) => {
+        if let Some(value) = event.output() {
+          return Ok(State::Output(value));
+        }
+      }
+      Step::NeedsInput => return Ok(State::NeedsInput),
+      Step::Halted => return Ok(State::Halted),
+    }
+  }
+}
+
+fn address(value: i64) -> Option<usize> {
+  if value >= 0 {
+    Some(value as usize)
+  } else {
+    None
+  }
+}
+
+fn read(program: &Program, address_value: i64) -> Option<i64> {
+  address(address_value).map(|address| program.memory().get(address))
+}
+
+fn write(program: &mut Program, address_value: i64, value: i64) -> Option<()> {
+  let address = address(address_value)?;
+  program.set_cell(address, value).ok()
+}
+
+fn intact(program: &Program, ptr: usize, size: usize) -> bool {
+  (ptr..ptr + size).all(|address| program.memory().get(address) == CODE[address])
+}
+";
+
+// Every instruction reachable from 0, following fallthrough and any immediate operand that
+// points into the program: jump targets and return addresses pushed before a call.
+fn reachable(code: &[i64]) -> BTreeMap<usize, Item> {
+  let mut items = BTreeMap::new();
+  let mut pending = vec![0];
+  while let Some(address) = pending.pop() {
+    if items.contains_key(&address) {
+      continue;
+    }
+    let item = match decode_at(code, address) {
+      Some(item) => item,
+      None => continue,
+    };
+    if let Item::Instruction {
+      opcode, operands, ..
+    } = &item
+    {
+      if *opcode != Opcode::Exit {
+        pending.push(address + opcode.size());
+      }
+      for (mode, &value) in opcode.modes().iter().zip(operands) {
+        if *mode == ParamMode::Immediate && value >= 0 && (value as usize) < code.len() {
+          pending.push(value as usize);
+        }
+      }
+    }
+    items.insert(address, item);
+  }
+  items
+}
+
+// A relative address that overflows falls back to the interpreter, which reports the error
+fn read(mode: ParamMode, value: i64) -> String {
+  match mode {
+    ParamMode::Position => format!("read(program, {})?", value),
+    ParamMode::Immediate => value.to_string(),
+    ParamMode::Relative => format!("read(program, rb.checked_add({})?)?", value),
+  }
+}
+
+fn target(mode: ParamMode, value: i64) -> String {
+  match mode {
+    ParamMode::Relative => format!("rb.checked_add({})?", value),
+    _ => value.to_string(),
+  }
+}
+
+fn emit_instruction(
+  out: &mut fmt::Formatter,
+  address: usize,
+  opcode: Opcode,
+  operands: &[i64],
+) -> fmt::Result {
+  let next = address + opcode.size();
+  let arg = |idx: usize| read(opcode.modes()[idx], operands[idx]);
+  let dest = || {
+    let idx = operands.len() - 1;
+    target(opcode.modes()[idx], operands[idx])
+  };
+  let binary = |out: &mut fmt::Formatter, result: &str| {
+    writeln!(
+      out,
+      "        let (a, b): (i64, i64) = ({}, {});",
+      arg(0),
+      arg(1)
+    )?;
+    writeln!(out, "        write(program, {}, {})?;", dest(), result)?;
+    writeln!(out, "        *ptr = {};", next)
+  };
+  match opcode {
+    // on overflow the interpreter re-runs the instruction under the program's arithmetic policy
+    Opcode::Add(_) => binary(out, "a.checked_add(b)?"),
+    Opcode::Mul(_) => binary(out, "a.checked_mul(b)?"),
+    Opcode::LessThan(_) => binary(out, "(a < b) as i64"),
+    Opcode::Equals(_) => binary(out, "(a == b) as i64"),
+    Opcode::Input(_) => {
+      writeln!(out, "        let value = match program.input().front() {{")?;
+      writeln!(out, "          Some(&value) => value,")?;
+      writeln!(out, "          None => return Some(State::NeedsInput),")?;
+      writeln!(out, "        }};")?;
+      writeln!(out, "        write(program, {}, value)?;", dest())?;
+      writeln!(out, "        program.pop_input();")?;
+      writeln!(out, "        *ptr = {};", next)
+    }
+    Opcode::Output(_) => {
+      writeln!(out, "        let value = {};", arg(0))?;
+      writeln!(out, "        *ptr = {};", next)?;
+      writeln!(out, "        return Some(State::Output(value));")
+    }
+    Opcode::JumpIfTrue(_) | Opcode::JumpIfFalse(_) => {
+      let test = if let Opcode::JumpIfTrue(_) = opcode {
+        "!="
+      } else {
+        "=="
+      };
+      writeln!(
+        out,
+        "        *ptr = if {} {} 0 {{ address({})? }} else {{ {} }};",
+        arg(0),
+        test,
+        arg(1),
+        next
+      )
+    }
+    Opcode::RelativeBase(_) => {
+      writeln!(out, "        *rb = rb.checked_add({})?;", arg(0))?;
+      writeln!(out, "        *ptr = {};", next)
+    }
+    Opcode::Exit => writeln!(out, "        return Some(State::Halted);"),
+  }
+}
+
+fn emit(out: &mut fmt::Formatter, code: &[i64]) -> fmt::Result {
+  writeln!(out, "// Generated by intcode-transpile, do not edit.")?;
+  writeln!(out, "#![allow(unused, clippy::all)]")?;
+  writeln!(out)?;
+  writeln!(out, "use advent19::{{IntcodeError, Program, State, Step}};")?;
+  writeln!(out)?;
+  writeln!(out, "pub const CODE: &[i64] = &[")?;
+  for chunk in code.chunks(VALUES_PER_LINE) {
+    let values: Vec<_> = chunk.iter().map(i64::to_string).collect();
+    writeln!(out, "  {},", values.join(", "))?;
+  }
+  writeln!(out, "];")?;
+  writeln!(out)?;
+  write!(out, "{}", RUNTIME)?;
+  writeln!(out)?;
+  writeln!(
+    out,
+    "fn execute(program: &mut Program, ptr: &mut usize, rb: &mut i64) -> Option<State> {{"
+  )?;
+  writeln!(out, "  loop {{")?;
+  writeln!(out, "    match *ptr {{")?;
+  for (address, item) in reachable(code) {
+    if let Item::Instruction {
+      opcode, operands, ..
+    } = &item
+    {
+      writeln!(out, "      // {}", item.to_string().trim_start())?;
+      writeln!(
+        out,
+        "      {} if intact(program, {}, {}) => {{",
+        address,
+        address,
+        opcode.size()
+      )?;
+      emit_instruction(out, address, *opcode, operands)?;
+      writeln!(out, "      }}")?;
+    }
+  }
+  writeln!(out, "      _ => return None,")?;
+  writeln!(out, "    }}")?;
+  writeln!(out, "  }}")?;
+  writeln!(out, "}}")
+}
+
+/// Rust source for a module with a `run` function that executes `code` natively
+pub struct Transpiled<'a> {
+  code: &'a [i64],
+}
+
+impl fmt::Display for Transpiled<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    emit(f, self.code)
+  }
+}
+
+pub fn transpile(code: &[i64]) -> Transpiled<'_> {
+  Transpiled { code }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reachable_instructions() {
+    // jt #1, #6; .data 7, 0, 0; out #1; hlt
+    let code = [1105, 1, 6, 7, 0, 0, 104, 1, 99];
+    assert_eq!(
+      reachable(&code).keys().copied().collect::<Vec<_>>(),
+      vec![0, 1, 3, 6, 8]
+    );
+  }
+
+  #[test]
+  fn instructions() {
+    let source = transpile(&[109, 1, 204, -1, 21101, 2, 3, 0, 1006, 0, 0, 99]).to_string();
+    let arm = |address: usize| {
+      let start = source.find(&format!("      {} if", address)).unwrap();
+      let end = start + source[start..].find("      }\n").unwrap();
+      source[start..end]
+        .lines()
+        .skip(1)
+        .map(str::trim)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(arm(0), vec!["*rb = rb.checked_add(1)?;", "*ptr = 2;"]);
+    assert_eq!(
+      arm(2),
+      vec![
+        "let value = read(program, rb.checked_add(-1)?)?;",
+        "*ptr = 4;",
+        "return Some(State::Output(value));"
+      ]
+    );
+    assert_eq!(
+      arm(4),
+      vec![
+        "let (a, b): (i64, i64) = (2, 3);",
+        "write(program, rb.checked_add(0)?, a.checked_add(b)?)?;",
+        "*ptr = 8;"
+      ]
+    );
+    assert_eq!(
+      arm(8),
+      vec!["*ptr = if read(program, 0)? == 0 { address(0)? } else { 11 };"]
+    );
+  }
+  #[test]
+  fn non_canonical_words() {
+    // add #1, #1, 7; jt #1, #8; .data 0; hlt with a mode digit
+    let source = transpile(&[1101, 1, 1, 7, 1105, 1, 8, 0, 1099]).to_string();
+    assert!(source.contains(
+      "      // 8: hlt\n      8 if intact(program, 8, 1) => {\n        return Some(State::Halted);\n"
+    ));
+  }
+}
@@ -0,0 +1,1791 @@
+// Generated by intcode-transpile, do not edit.
+#![allow(unused, clippy::all)]
+
+use advent19::{IntcodeError, Program, State, Step};
+
+pub const CODE: &[i64] = &[
+  1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1102, 1, 3, 1000, 109,
+  988, 209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005,
+  63, 65, 1008, 1000, 2, 63, 1005, 63, 904, 1008, 1000, 0, 63, 1005, 63, 58,
+  4, 25, 104, 0, 99, 4, 0, 104, 0, 99, 4, 17, 104, 0, 99, 0,
+  0, 1102, 521, 1, 1028, 1101, 0, 33, 1011, 1101, 0, 22, 1006, 1101, 28, 0,
+  1018, 1102, 37, 1, 1008, 1102, 1, 20, 1019, 1101, 0, 405, 1026, 1101, 25, 0,
+  1015, 1101, 330, 0, 1023, 1101, 0, 29, 1016, 1101, 0, 560, 1025, 1101, 24, 0,
+  1017, 1102, 516, 1, 1029, 1102, 333, 1, 1022, 1102, 1, 34, 1012, 1101, 0, 402,
+  1027, 1101, 0, 1, 1021, 1102, 36, 1, 1013, 1102, 30, 1, 1002, 1101, 21, 0,
+  1000, 1102, 1, 23, 1005, 1102, 39, 1, 1003, 1102, 1, 32, 1007, 1102, 26, 1,
+  1004, 1101, 565, 0, 1024, 1101, 0, 0, 1020, 1101, 0, 31, 1014, 1101, 27, 0,
+  1001, 1101, 0, 38, 1009, 1101, 0, 35, 1010, 109, -3, 2102, 1, 10, 63, 1008,
+  63, 32, 63, 1005, 63, 203, 4, 187, 1106, 0, 207, 1001, 64, 1, 64, 1002,
+  64, 2, 64, 109, 26, 21108, 40, 40, -4, 1005, 1019, 229, 4, 213, 1001, 64,
+  1, 64, 1105, 1, 229, 1002, 64, 2, 64, 109, -20, 2102, 1, -3, 63, 1008,
+  63, 22, 63, 1005, 63, 253, 1001, 64, 1, 64, 1105, 1, 255, 4, 235, 1002,
+  64, 2, 64, 109, -10, 1208, 10, 39, 63, 1005, 63, 277, 4, 261, 1001, 64,
+  1, 64, 1106, 0, 277, 1002, 64, 2, 64, 109, 15, 2107, 20, -8, 63, 1005,
+  63, 299, 4, 283, 1001, 64, 1, 64, 1106, 0, 299, 1002, 64, 2, 64, 109,
+  -8, 1208, 3, 40, 63, 1005, 63, 315, 1106, 0, 321, 4, 305, 1001, 64, 1,
+  64, 1002, 64, 2, 64, 109, 29, 2105, 1, -6, 1106, 0, 339, 4, 327, 1001,
+  64, 1, 64, 1002, 64, 2, 64, 109, -18, 1205, 10, 353, 4, 345, 1106, 0,
+  357, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 11, 1206, -1, 373, 1001, 64,
+  1, 64, 1105, 1, 375, 4, 363, 1002, 64, 2, 64, 109, -2, 1205, 0, 391,
+  1001, 64, 1, 64, 1106, 0, 393, 4, 381, 1002, 64, 2, 64, 109, 10, 2106,
+  0, -3, 1106, 0, 411, 4, 399, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+  -18, 21108, 41, 39, 3, 1005, 1015, 427, 1105, 1, 433, 4, 417, 1001, 64, 1,
+  64, 1002, 64, 2, 64, 109, -7, 21101, 42, 0, 6, 1008, 1011, 45, 63, 1005,
+  63, 457, 1001, 64, 1, 64, 1106, 0, 459, 4, 439, 1002, 64, 2, 64, 109,
+  -14, 2101, 0, 9, 63, 1008, 63, 21, 63, 1005, 63, 481, 4, 465, 1105, 1,
+  485, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 22, 1207, -7, 21, 63, 1005,
+  63, 505, 1001, 64, 1, 64, 1106, 0, 507, 4, 491, 1002, 64, 2, 64, 109,
+  15, 2106, 0, 0, 4, 513, 1106, 0, 525, 1001, 64, 1, 64, 1002, 64, 2,
+  64, 109, -14, 21101, 43, 0, -1, 1008, 1013, 43, 63, 1005, 63, 551, 4, 531,
+  1001, 64, 1, 64, 1106, 0, 551, 1002, 64, 2, 64, 109, 10, 2105, 1, 0,
+  4, 557, 1106, 0, 569, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -12, 21102,
+  44, 1, 3, 1008, 1015, 44, 63, 1005, 63, 595, 4, 575, 1001, 64, 1, 64,
+  1105, 1, 595, 1002, 64, 2, 64, 109, -4, 1201, -8, 0, 63, 1008, 63, 21,
+  63, 1005, 63, 621, 4, 601, 1001, 64, 1, 64, 1106, 0, 621, 1002, 64, 2,
+  64, 109, 5, 2108, 37, -5, 63, 1005, 63, 639, 4, 627, 1105, 1, 643, 1001,
+  64, 1, 64, 1002, 64, 2, 64, 109, -14, 1202, 1, 1, 63, 1008, 63, 21,
+  63, 1005, 63, 669, 4, 649, 1001, 64, 1, 64, 1105, 1, 669, 1002, 64, 2,
+  64, 109, -2, 1207, 7, 27, 63, 1005, 63, 691, 4, 675, 1001, 64, 1, 64,
+  1106, 0, 691, 1002, 64, 2, 64, 109, 13, 2107, 33, -3, 63, 1005, 63, 711,
+  1001, 64, 1, 64, 1105, 1, 713, 4, 697, 1002, 64, 2, 64, 109, 19, 1206,
+  -9, 727, 4, 719, 1105, 1, 731, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+  -24, 1202, 0, 1, 63, 1008, 63, 20, 63, 1005, 63, 755, 1001, 64, 1, 64,
+  1106, 0, 757, 4, 737, 1002, 64, 2, 64, 109, 8, 21102, 45, 1, -3, 1008,
+  1010, 46, 63, 1005, 63, 781, 1001, 64, 1, 64, 1106, 0, 783, 4, 763, 1002,
+  64, 2, 64, 109, -15, 2108, 40, 10, 63, 1005, 63, 799, 1105, 1, 805, 4,
+  789, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 20, 21107, 46, 45, -1, 1005,
+  1017, 821, 1106, 0, 827, 4, 811, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+  -23, 1201, 6, 0, 63, 1008, 63, 29, 63, 1005, 63, 847, 1106, 0, 853, 4,
+  833, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 17, 21107, 47, 48, 2, 1005,
+  1014, 875, 4, 859, 1001, 64, 1, 64, 1106, 0, 875, 1002, 64, 2, 64, 109,
+  -10, 2101, 0, -2, 63, 1008, 63, 20, 63, 1005, 63, 895, 1105, 1, 901, 4,
+  881, 1001, 64, 1, 64, 4, 64, 99, 21102, 27, 1, 1, 21101, 0, 915, 0,
+  1105, 1, 922, 21201, 1, 37574, 1, 204, 1, 99, 109, 3, 1207, -2, 3, 63,
+  1005, 63, 964, 21201, -2, -1, 1, 21102, 942, 1, 0, 1105, 1, 922, 22102, 1,
+  1, -1, 21201, -2, -3, 1, 21101, 957, 0, 0, 1105, 1, 922, 22201, 1, -1,
+  -2, 1105, 1, 968, 21201, -2, 0, -2, 109, -3, 2105, 1, 0,
+];
+
+pub fn run(program: &mut Program) -> Result<State, IntcodeError> {
+  loop {
+    let (mut ptr, mut rb) = (program.ptr(), program.relative_base());
+    let state = execute(program, &mut ptr, &mut rb);
+    program.set_ptr(ptr);
+    program.set_relative_base(rb);
+    if let Some(state) = state {
+      return Ok(state);
+    }
+    match program.step()? {
+      Step::Executed(event) => {
+        if let Some(value) = event.output() {
+          return Ok(State::Output(value));
+        }
+      }
+      Step::NeedsInput => return Ok(State::NeedsInput),
+      Step::Halted => return Ok(State::Halted),
+    }
+  }
+}
+
+fn address(value: i64) -> Option<usize> {
+  if value >= 0 {
+    Some(value as usize)
+  } else {
+    None
+  }
+}
+
+fn read(program: &Program, address_value: i64) -> Option<i64> {
+  address(address_value).map(|address| program.memory().get(address))
+}
+
+fn write(program: &mut Program, address_value: i64, value: i64) -> Option<()> {
+  let address = address(address_value)?;
+  program.set_cell(address, value).ok()
+}
+
+fn intact(program: &Program, ptr: usize, size: usize) -> bool {
+  (ptr..ptr + size).all(|address| program.memory().get(address) == CODE[address])
+}
+
+fn execute(program: &mut Program, ptr: &mut usize, rb: &mut i64) -> Option<State> {
+  loop {
+    match *ptr {
+      // 0: mul #34463338, #34463338, 63
+      0 if intact(program, 0, 4) => {
+        let (a, b): (i64, i64) = (34463338, 34463338);
+        write(program, 63, a.checked_mul(b)?)?;
+        *ptr = 4;
+      }
+      // 4: lt 63, #34463338, 63
+      4 if intact(program, 4, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 34463338);
+        write(program, 63, (a < b) as i64)?;
+        *ptr = 8;
+      }
+      // 8: jt 63, #53
+      8 if intact(program, 8, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(53)? } else { 11 };
+      }
+      // 11: mul #1, #3, 1000
+      11 if intact(program, 11, 4) => {
+        let (a, b): (i64, i64) = (1, 3);
+        write(program, 1000, a.checked_mul(b)?)?;
+        *ptr = 15;
+      }
+      // 13: in 1000
+      13 if intact(program, 13, 2) => {
+        let value = match program.input().front() {
+          Some(&value) => value,
+          None => return Some(State::NeedsInput),
+        };
+        write(program, 1000, value)?;
+        program.pop_input();
+        *ptr = 15;
+      }
+      // 15: arb #988
+      15 if intact(program, 15, 2) => {
+        *rb = rb.checked_add(988)?;
+        *ptr = 17;
+      }
+      // 17: arb @12
+      17 if intact(program, 17, 2) => {
+        *rb = rb.checked_add(read(program, rb.checked_add(12)?)?)?;
+        *ptr = 19;
+      }
+      // 19: arb 1000
+      19 if intact(program, 19, 2) => {
+        *rb = rb.checked_add(read(program, 1000)?)?;
+        *ptr = 21;
+      }
+      // 21: arb @6
+      21 if intact(program, 21, 2) => {
+        *rb = rb.checked_add(read(program, rb.checked_add(6)?)?)?;
+        *ptr = 23;
+      }
+      // 22: jf 209, 3
+      22 if intact(program, 22, 3) => {
+        *ptr = if read(program, 209)? == 0 { address(read(program, 3)?)? } else { 25 };
+      }
+      // 23: arb @3
+      23 if intact(program, 23, 2) => {
+        *rb = rb.checked_add(read(program, rb.checked_add(3)?)?)?;
+        *ptr = 25;
+      }
+      // 24: in 203
+      24 if intact(program, 24, 2) => {
+        let value = match program.input().front() {
+          Some(&value) => value,
+          None => return Some(State::NeedsInput),
+        };
+        write(program, 203, value)?;
+        program.pop_input();
+        *ptr = 26;
+      }
+      // 25: in @0
+      25 if intact(program, 25, 2) => {
+        let value = match program.input().front() {
+          Some(&value) => value,
+          None => return Some(State::NeedsInput),
+        };
+        write(program, rb.checked_add(0)?, value)?;
+        program.pop_input();
+        *ptr = 27;
+      }
+      // 27: eq 1000, #1, 63
+      27 if intact(program, 27, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1000)?, 1);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 31;
+      }
+      // 29: add 63, 1005, 63
+      29 if intact(program, 29, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, read(program, 1005)?);
+        write(program, 63, a.checked_add(b)?)?;
+        *ptr = 33;
+      }
+      // 31: jt 63, #65
+      31 if intact(program, 31, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(65)? } else { 34 };
+      }
+      // 34: eq 1000, #2, 63
+      34 if intact(program, 34, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1000)?, 2);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 38;
+      }
+      // 36: mul 63, 1005, 63
+      36 if intact(program, 36, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, read(program, 1005)?);
+        write(program, 63, a.checked_mul(b)?)?;
+        *ptr = 40;
+      }
+      // 38: jt 63, #904
+      38 if intact(program, 38, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(904)? } else { 41 };
+      }
+      // 41: eq 1000, #0, 63
+      41 if intact(program, 41, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1000)?, 0);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 45;
+      }
+      // 45: jt 63, #58
+      45 if intact(program, 45, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(58)? } else { 48 };
+      }
+      // 48: out 25
+      48 if intact(program, 48, 2) => {
+        let value = read(program, 25)?;
+        *ptr = 50;
+        return Some(State::Output(value));
+      }
+      // 50: out #0
+      50 if intact(program, 50, 2) => {
+        let value = 0;
+        *ptr = 52;
+        return Some(State::Output(value));
+      }
+      // 52: hlt
+      52 if intact(program, 52, 1) => {
+        return Some(State::Halted);
+      }
+      // 53: out 0
+      53 if intact(program, 53, 2) => {
+        let value = read(program, 0)?;
+        *ptr = 55;
+        return Some(State::Output(value));
+      }
+      // 55: out #0
+      55 if intact(program, 55, 2) => {
+        let value = 0;
+        *ptr = 57;
+        return Some(State::Output(value));
+      }
+      // 57: hlt
+      57 if intact(program, 57, 1) => {
+        return Some(State::Halted);
+      }
+      // 58: out 17
+      58 if intact(program, 58, 2) => {
+        let value = read(program, 17)?;
+        *ptr = 60;
+        return Some(State::Output(value));
+      }
+      // 60: out #0
+      60 if intact(program, 60, 2) => {
+        let value = 0;
+        *ptr = 62;
+        return Some(State::Output(value));
+      }
+      // 62: hlt
+      62 if intact(program, 62, 1) => {
+        return Some(State::Halted);
+      }
+      // 65: mul #521, #1, 1028
+      65 if intact(program, 65, 4) => {
+        let (a, b): (i64, i64) = (521, 1);
+        write(program, 1028, a.checked_mul(b)?)?;
+        *ptr = 69;
+      }
+      // 69: add #0, #33, 1011
+      69 if intact(program, 69, 4) => {
+        let (a, b): (i64, i64) = (0, 33);
+        write(program, 1011, a.checked_add(b)?)?;
+        *ptr = 73;
+      }
+      // 73: add #0, #22, 1006
+      73 if intact(program, 73, 4) => {
+        let (a, b): (i64, i64) = (0, 22);
+        write(program, 1006, a.checked_add(b)?)?;
+        *ptr = 77;
+      }
+      // 77: add #28, #0, 1018
+      77 if intact(program, 77, 4) => {
+        let (a, b): (i64, i64) = (28, 0);
+        write(program, 1018, a.checked_add(b)?)?;
+        *ptr = 81;
+      }
+      // 81: mul #37, #1, 1008
+      81 if intact(program, 81, 4) => {
+        let (a, b): (i64, i64) = (37, 1);
+        write(program, 1008, a.checked_mul(b)?)?;
+        *ptr = 85;
+      }
+      // 85: mul #1, #20, 1019
+      85 if intact(program, 85, 4) => {
+        let (a, b): (i64, i64) = (1, 20);
+        write(program, 1019, a.checked_mul(b)?)?;
+        *ptr = 89;
+      }
+      // 89: add #0, #405, 1026
+      89 if intact(program, 89, 4) => {
+        let (a, b): (i64, i64) = (0, 405);
+        write(program, 1026, a.checked_add(b)?)?;
+        *ptr = 93;
+      }
+      // 93: add #25, #0, 1015
+      93 if intact(program, 93, 4) => {
+        let (a, b): (i64, i64) = (25, 0);
+        write(program, 1015, a.checked_add(b)?)?;
+        *ptr = 97;
+      }
+      // 97: add #330, #0, 1023
+      97 if intact(program, 97, 4) => {
+        let (a, b): (i64, i64) = (330, 0);
+        write(program, 1023, a.checked_add(b)?)?;
+        *ptr = 101;
+      }
+      // 101: add #0, #29, 1016
+      101 if intact(program, 101, 4) => {
+        let (a, b): (i64, i64) = (0, 29);
+        write(program, 1016, a.checked_add(b)?)?;
+        *ptr = 105;
+      }
+      // 105: add #0, #560, 1025
+      105 if intact(program, 105, 4) => {
+        let (a, b): (i64, i64) = (0, 560);
+        write(program, 1025, a.checked_add(b)?)?;
+        *ptr = 109;
+      }
+      // 109: add #24, #0, 1017
+      109 if intact(program, 109, 4) => {
+        let (a, b): (i64, i64) = (24, 0);
+        write(program, 1017, a.checked_add(b)?)?;
+        *ptr = 113;
+      }
+      // 113: mul #516, #1, 1029
+      113 if intact(program, 113, 4) => {
+        let (a, b): (i64, i64) = (516, 1);
+        write(program, 1029, a.checked_mul(b)?)?;
+        *ptr = 117;
+      }
+      // 117: mul #333, #1, 1022
+      117 if intact(program, 117, 4) => {
+        let (a, b): (i64, i64) = (333, 1);
+        write(program, 1022, a.checked_mul(b)?)?;
+        *ptr = 121;
+      }
+      // 121: mul #1, #34, 1012
+      121 if intact(program, 121, 4) => {
+        let (a, b): (i64, i64) = (1, 34);
+        write(program, 1012, a.checked_mul(b)?)?;
+        *ptr = 125;
+      }
+      // 125: add #0, #402, 1027
+      125 if intact(program, 125, 4) => {
+        let (a, b): (i64, i64) = (0, 402);
+        write(program, 1027, a.checked_add(b)?)?;
+        *ptr = 129;
+      }
+      // 129: add #0, #1, 1021
+      129 if intact(program, 129, 4) => {
+        let (a, b): (i64, i64) = (0, 1);
+        write(program, 1021, a.checked_add(b)?)?;
+        *ptr = 133;
+      }
+      // 133: mul #36, #1, 1013
+      133 if intact(program, 133, 4) => {
+        let (a, b): (i64, i64) = (36, 1);
+        write(program, 1013, a.checked_mul(b)?)?;
+        *ptr = 137;
+      }
+      // 137: mul #30, #1, 1002
+      137 if intact(program, 137, 4) => {
+        let (a, b): (i64, i64) = (30, 1);
+        write(program, 1002, a.checked_mul(b)?)?;
+        *ptr = 141;
+      }
+      // 141: add #21, #0, 1000
+      141 if intact(program, 141, 4) => {
+        let (a, b): (i64, i64) = (21, 0);
+        write(program, 1000, a.checked_add(b)?)?;
+        *ptr = 145;
+      }
+      // 145: mul #1, #23, 1005
+      145 if intact(program, 145, 4) => {
+        let (a, b): (i64, i64) = (1, 23);
+        write(program, 1005, a.checked_mul(b)?)?;
+        *ptr = 149;
+      }
+      // 149: mul #39, #1, 1003
+      149 if intact(program, 149, 4) => {
+        let (a, b): (i64, i64) = (39, 1);
+        write(program, 1003, a.checked_mul(b)?)?;
+        *ptr = 153;
+      }
+      // 153: mul #1, #32, 1007
+      153 if intact(program, 153, 4) => {
+        let (a, b): (i64, i64) = (1, 32);
+        write(program, 1007, a.checked_mul(b)?)?;
+        *ptr = 157;
+      }
+      // 157: mul #26, #1, 1004
+      157 if intact(program, 157, 4) => {
+        let (a, b): (i64, i64) = (26, 1);
+        write(program, 1004, a.checked_mul(b)?)?;
+        *ptr = 161;
+      }
+      // 161: add #565, #0, 1024
+      161 if intact(program, 161, 4) => {
+        let (a, b): (i64, i64) = (565, 0);
+        write(program, 1024, a.checked_add(b)?)?;
+        *ptr = 165;
+      }
+      // 165: add #0, #0, 1020
+      165 if intact(program, 165, 4) => {
+        let (a, b): (i64, i64) = (0, 0);
+        write(program, 1020, a.checked_add(b)?)?;
+        *ptr = 169;
+      }
+      // 169: add #0, #31, 1014
+      169 if intact(program, 169, 4) => {
+        let (a, b): (i64, i64) = (0, 31);
+        write(program, 1014, a.checked_add(b)?)?;
+        *ptr = 173;
+      }
+      // 173: add #27, #0, 1001
+      173 if intact(program, 173, 4) => {
+        let (a, b): (i64, i64) = (27, 0);
+        write(program, 1001, a.checked_add(b)?)?;
+        *ptr = 177;
+      }
+      // 177: add #0, #38, 1009
+      177 if intact(program, 177, 4) => {
+        let (a, b): (i64, i64) = (0, 38);
+        write(program, 1009, a.checked_add(b)?)?;
+        *ptr = 181;
+      }
+      // 181: add #0, #35, 1010
+      181 if intact(program, 181, 4) => {
+        let (a, b): (i64, i64) = (0, 35);
+        write(program, 1010, a.checked_add(b)?)?;
+        *ptr = 185;
+      }
+      // 185: arb #-3
+      185 if intact(program, 185, 2) => {
+        *rb = rb.checked_add(-3)?;
+        *ptr = 187;
+      }
+      // 187: mul #1, @10, 63
+      187 if intact(program, 187, 4) => {
+        let (a, b): (i64, i64) = (1, read(program, rb.checked_add(10)?)?);
+        write(program, 63, a.checked_mul(b)?)?;
+        *ptr = 191;
+      }
+      // 191: eq 63, #32, 63
+      191 if intact(program, 191, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 32);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 195;
+      }
+      // 195: jt 63, #203
+      195 if intact(program, 195, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(203)? } else { 198 };
+      }
+      // 198: out 187
+      198 if intact(program, 198, 2) => {
+        let value = read(program, 187)?;
+        *ptr = 200;
+        return Some(State::Output(value));
+      }
+      // 200: jf #0, #207
+      200 if intact(program, 200, 3) => {
+        *ptr = if 0 == 0 { address(207)? } else { 203 };
+      }
+      // 203: add 64, #1, 64
+      203 if intact(program, 203, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 207;
+      }
+      // 207: mul 64, #2, 64
+      207 if intact(program, 207, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 211;
+      }
+      // 211: arb #26
+      211 if intact(program, 211, 2) => {
+        *rb = rb.checked_add(26)?;
+        *ptr = 213;
+      }
+      // 213: eq #40, #40, @-4
+      213 if intact(program, 213, 4) => {
+        let (a, b): (i64, i64) = (40, 40);
+        write(program, rb.checked_add(-4)?, (a == b) as i64)?;
+        *ptr = 217;
+      }
+      // 217: jt 1019, #229
+      217 if intact(program, 217, 3) => {
+        *ptr = if read(program, 1019)? != 0 { address(229)? } else { 220 };
+      }
+      // 220: out 213
+      220 if intact(program, 220, 2) => {
+        let value = read(program, 213)?;
+        *ptr = 222;
+        return Some(State::Output(value));
+      }
+      // 222: add 64, #1, 64
+      222 if intact(program, 222, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 226;
+      }
+      // 226: jt #1, #229
+      226 if intact(program, 226, 3) => {
+        *ptr = if 1 != 0 { address(229)? } else { 229 };
+      }
+      // 229: mul 64, #2, 64
+      229 if intact(program, 229, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 233;
+      }
+      // 233: arb #-20
+      233 if intact(program, 233, 2) => {
+        *rb = rb.checked_add(-20)?;
+        *ptr = 235;
+      }
+      // 235: mul #1, @-3, 63
+      235 if intact(program, 235, 4) => {
+        let (a, b): (i64, i64) = (1, read(program, rb.checked_add(-3)?)?);
+        write(program, 63, a.checked_mul(b)?)?;
+        *ptr = 239;
+      }
+      // 239: eq 63, #22, 63
+      239 if intact(program, 239, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 22);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 243;
+      }
+      // 243: jt 63, #253
+      243 if intact(program, 243, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(253)? } else { 246 };
+      }
+      // 246: add 64, #1, 64
+      246 if intact(program, 246, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 250;
+      }
+      // 250: jt #1, #255
+      250 if intact(program, 250, 3) => {
+        *ptr = if 1 != 0 { address(255)? } else { 253 };
+      }
+      // 253: out 235
+      253 if intact(program, 253, 2) => {
+        let value = read(program, 235)?;
+        *ptr = 255;
+        return Some(State::Output(value));
+      }
+      // 255: mul 64, #2, 64
+      255 if intact(program, 255, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 259;
+      }
+      // 259: arb #-10
+      259 if intact(program, 259, 2) => {
+        *rb = rb.checked_add(-10)?;
+        *ptr = 261;
+      }
+      // 261: eq @10, #39, 63
+      261 if intact(program, 261, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(10)?)?, 39);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 265;
+      }
+      // 265: jt 63, #277
+      265 if intact(program, 265, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(277)? } else { 268 };
+      }
+      // 268: out 261
+      268 if intact(program, 268, 2) => {
+        let value = read(program, 261)?;
+        *ptr = 270;
+        return Some(State::Output(value));
+      }
+      // 270: add 64, #1, 64
+      270 if intact(program, 270, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 274;
+      }
+      // 274: jf #0, #277
+      274 if intact(program, 274, 3) => {
+        *ptr = if 0 == 0 { address(277)? } else { 277 };
+      }
+      // 277: mul 64, #2, 64
+      277 if intact(program, 277, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 281;
+      }
+      // 281: arb #15
+      281 if intact(program, 281, 2) => {
+        *rb = rb.checked_add(15)?;
+        *ptr = 283;
+      }
+      // 283: lt #20, @-8, 63
+      283 if intact(program, 283, 4) => {
+        let (a, b): (i64, i64) = (20, read(program, rb.checked_add(-8)?)?);
+        write(program, 63, (a < b) as i64)?;
+        *ptr = 287;
+      }
+      // 287: jt 63, #299
+      287 if intact(program, 287, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(299)? } else { 290 };
+      }
+      // 290: out 283
+      290 if intact(program, 290, 2) => {
+        let value = read(program, 283)?;
+        *ptr = 292;
+        return Some(State::Output(value));
+      }
+      // 292: add 64, #1, 64
+      292 if intact(program, 292, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 296;
+      }
+      // 296: jf #0, #299
+      296 if intact(program, 296, 3) => {
+        *ptr = if 0 == 0 { address(299)? } else { 299 };
+      }
+      // 299: mul 64, #2, 64
+      299 if intact(program, 299, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 303;
+      }
+      // 303: arb #-8
+      303 if intact(program, 303, 2) => {
+        *rb = rb.checked_add(-8)?;
+        *ptr = 305;
+      }
+      // 305: eq @3, #40, 63
+      305 if intact(program, 305, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(3)?)?, 40);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 309;
+      }
+      // 309: jt 63, #315
+      309 if intact(program, 309, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(315)? } else { 312 };
+      }
+      // 312: jf #0, #321
+      312 if intact(program, 312, 3) => {
+        *ptr = if 0 == 0 { address(321)? } else { 315 };
+      }
+      // 315: out 305
+      315 if intact(program, 315, 2) => {
+        let value = read(program, 305)?;
+        *ptr = 317;
+        return Some(State::Output(value));
+      }
+      // 317: add 64, #1, 64
+      317 if intact(program, 317, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 321;
+      }
+      // 321: mul 64, #2, 64
+      321 if intact(program, 321, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 325;
+      }
+      // 325: arb #29
+      325 if intact(program, 325, 2) => {
+        *rb = rb.checked_add(29)?;
+        *ptr = 327;
+      }
+      // 327: jt #1, @-6
+      327 if intact(program, 327, 3) => {
+        *ptr = if 1 != 0 { address(read(program, rb.checked_add(-6)?)?)? } else { 330 };
+      }
+      // 330: jf #0, #339
+      330 if intact(program, 330, 3) => {
+        *ptr = if 0 == 0 { address(339)? } else { 333 };
+      }
+      // 333: out 327
+      333 if intact(program, 333, 2) => {
+        let value = read(program, 327)?;
+        *ptr = 335;
+        return Some(State::Output(value));
+      }
+      // 335: add 64, #1, 64
+      335 if intact(program, 335, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 339;
+      }
+      // 339: mul 64, #2, 64
+      339 if intact(program, 339, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 343;
+      }
+      // 343: arb #-18
+      343 if intact(program, 343, 2) => {
+        *rb = rb.checked_add(-18)?;
+        *ptr = 345;
+      }
+      // 345: jt @10, #353
+      345 if intact(program, 345, 3) => {
+        *ptr = if read(program, rb.checked_add(10)?)? != 0 { address(353)? } else { 348 };
+      }
+      // 348: out 345
+      348 if intact(program, 348, 2) => {
+        let value = read(program, 345)?;
+        *ptr = 350;
+        return Some(State::Output(value));
+      }
+      // 350: jf #0, #357
+      350 if intact(program, 350, 3) => {
+        *ptr = if 0 == 0 { address(357)? } else { 353 };
+      }
+      // 353: add 64, #1, 64
+      353 if intact(program, 353, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 357;
+      }
+      // 357: mul 64, #2, 64
+      357 if intact(program, 357, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 361;
+      }
+      // 361: arb #11
+      361 if intact(program, 361, 2) => {
+        *rb = rb.checked_add(11)?;
+        *ptr = 363;
+      }
+      // 363: jf @-1, #373
+      363 if intact(program, 363, 3) => {
+        *ptr = if read(program, rb.checked_add(-1)?)? == 0 { address(373)? } else { 366 };
+      }
+      // 366: add 64, #1, 64
+      366 if intact(program, 366, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 370;
+      }
+      // 370: jt #1, #375
+      370 if intact(program, 370, 3) => {
+        *ptr = if 1 != 0 { address(375)? } else { 373 };
+      }
+      // 373: out 363
+      373 if intact(program, 373, 2) => {
+        let value = read(program, 363)?;
+        *ptr = 375;
+        return Some(State::Output(value));
+      }
+      // 375: mul 64, #2, 64
+      375 if intact(program, 375, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 379;
+      }
+      // 379: arb #-2
+      379 if intact(program, 379, 2) => {
+        *rb = rb.checked_add(-2)?;
+        *ptr = 381;
+      }
+      // 381: jt @0, #391
+      381 if intact(program, 381, 3) => {
+        *ptr = if read(program, rb.checked_add(0)?)? != 0 { address(391)? } else { 384 };
+      }
+      // 384: add 64, #1, 64
+      384 if intact(program, 384, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 388;
+      }
+      // 388: jf #0, #393
+      388 if intact(program, 388, 3) => {
+        *ptr = if 0 == 0 { address(393)? } else { 391 };
+      }
+      // 391: out 381
+      391 if intact(program, 391, 2) => {
+        let value = read(program, 381)?;
+        *ptr = 393;
+        return Some(State::Output(value));
+      }
+      // 393: mul 64, #2, 64
+      393 if intact(program, 393, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 397;
+      }
+      // 397: arb #10
+      397 if intact(program, 397, 2) => {
+        *rb = rb.checked_add(10)?;
+        *ptr = 399;
+      }
+      // 399: jf #0, @-3
+      399 if intact(program, 399, 3) => {
+        *ptr = if 0 == 0 { address(read(program, rb.checked_add(-3)?)?)? } else { 402 };
+      }
+      // 402: jf #0, #411
+      402 if intact(program, 402, 3) => {
+        *ptr = if 0 == 0 { address(411)? } else { 405 };
+      }
+      // 405: out 399
+      405 if intact(program, 405, 2) => {
+        let value = read(program, 399)?;
+        *ptr = 407;
+        return Some(State::Output(value));
+      }
+      // 407: add 64, #1, 64
+      407 if intact(program, 407, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 411;
+      }
+      // 411: mul 64, #2, 64
+      411 if intact(program, 411, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 415;
+      }
+      // 415: arb #-18
+      415 if intact(program, 415, 2) => {
+        *rb = rb.checked_add(-18)?;
+        *ptr = 417;
+      }
+      // 417: eq #41, #39, @3
+      417 if intact(program, 417, 4) => {
+        let (a, b): (i64, i64) = (41, 39);
+        write(program, rb.checked_add(3)?, (a == b) as i64)?;
+        *ptr = 421;
+      }
+      // 421: jt 1015, #427
+      421 if intact(program, 421, 3) => {
+        *ptr = if read(program, 1015)? != 0 { address(427)? } else { 424 };
+      }
+      // 424: jt #1, #433
+      424 if intact(program, 424, 3) => {
+        *ptr = if 1 != 0 { address(433)? } else { 427 };
+      }
+      // 427: out 417
+      427 if intact(program, 427, 2) => {
+        let value = read(program, 417)?;
+        *ptr = 429;
+        return Some(State::Output(value));
+      }
+      // 429: add 64, #1, 64
+      429 if intact(program, 429, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 433;
+      }
+      // 433: mul 64, #2, 64
+      433 if intact(program, 433, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 437;
+      }
+      // 437: arb #-7
+      437 if intact(program, 437, 2) => {
+        *rb = rb.checked_add(-7)?;
+        *ptr = 439;
+      }
+      // 439: add #42, #0, @6
+      439 if intact(program, 439, 4) => {
+        let (a, b): (i64, i64) = (42, 0);
+        write(program, rb.checked_add(6)?, a.checked_add(b)?)?;
+        *ptr = 443;
+      }
+      // 443: eq 1011, #45, 63
+      443 if intact(program, 443, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1011)?, 45);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 447;
+      }
+      // 447: jt 63, #457
+      447 if intact(program, 447, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(457)? } else { 450 };
+      }
+      // 450: add 64, #1, 64
+      450 if intact(program, 450, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 454;
+      }
+      // 454: jf #0, #459
+      454 if intact(program, 454, 3) => {
+        *ptr = if 0 == 0 { address(459)? } else { 457 };
+      }
+      // 457: out 439
+      457 if intact(program, 457, 2) => {
+        let value = read(program, 439)?;
+        *ptr = 459;
+        return Some(State::Output(value));
+      }
+      // 459: mul 64, #2, 64
+      459 if intact(program, 459, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 463;
+      }
+      // 463: arb #-14
+      463 if intact(program, 463, 2) => {
+        *rb = rb.checked_add(-14)?;
+        *ptr = 465;
+      }
+      // 465: add #0, @9, 63
+      465 if intact(program, 465, 4) => {
+        let (a, b): (i64, i64) = (0, read(program, rb.checked_add(9)?)?);
+        write(program, 63, a.checked_add(b)?)?;
+        *ptr = 469;
+      }
+      // 469: eq 63, #21, 63
+      469 if intact(program, 469, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 21);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 473;
+      }
+      // 473: jt 63, #481
+      473 if intact(program, 473, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(481)? } else { 476 };
+      }
+      // 476: out 465
+      476 if intact(program, 476, 2) => {
+        let value = read(program, 465)?;
+        *ptr = 478;
+        return Some(State::Output(value));
+      }
+      // 478: jt #1, #485
+      478 if intact(program, 478, 3) => {
+        *ptr = if 1 != 0 { address(485)? } else { 481 };
+      }
+      // 481: add 64, #1, 64
+      481 if intact(program, 481, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 485;
+      }
+      // 485: mul 64, #2, 64
+      485 if intact(program, 485, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 489;
+      }
+      // 489: arb #22
+      489 if intact(program, 489, 2) => {
+        *rb = rb.checked_add(22)?;
+        *ptr = 491;
+      }
+      // 491: lt @-7, #21, 63
+      491 if intact(program, 491, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(-7)?)?, 21);
+        write(program, 63, (a < b) as i64)?;
+        *ptr = 495;
+      }
+      // 495: jt 63, #505
+      495 if intact(program, 495, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(505)? } else { 498 };
+      }
+      // 498: add 64, #1, 64
+      498 if intact(program, 498, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 502;
+      }
+      // 502: jf #0, #507
+      502 if intact(program, 502, 3) => {
+        *ptr = if 0 == 0 { address(507)? } else { 505 };
+      }
+      // 505: out 491
+      505 if intact(program, 505, 2) => {
+        let value = read(program, 491)?;
+        *ptr = 507;
+        return Some(State::Output(value));
+      }
+      // 507: mul 64, #2, 64
+      507 if intact(program, 507, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 511;
+      }
+      // 511: arb #15
+      511 if intact(program, 511, 2) => {
+        *rb = rb.checked_add(15)?;
+        *ptr = 513;
+      }
+      // 513: jf #0, @0
+      513 if intact(program, 513, 3) => {
+        *ptr = if 0 == 0 { address(read(program, rb.checked_add(0)?)?)? } else { 516 };
+      }
+      // 516: out 513
+      516 if intact(program, 516, 2) => {
+        let value = read(program, 513)?;
+        *ptr = 518;
+        return Some(State::Output(value));
+      }
+      // 518: jf #0, #525
+      518 if intact(program, 518, 3) => {
+        *ptr = if 0 == 0 { address(525)? } else { 521 };
+      }
+      // 521: add 64, #1, 64
+      521 if intact(program, 521, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 525;
+      }
+      // 525: mul 64, #2, 64
+      525 if intact(program, 525, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 529;
+      }
+      // 529: arb #-14
+      529 if intact(program, 529, 2) => {
+        *rb = rb.checked_add(-14)?;
+        *ptr = 531;
+      }
+      // 531: add #43, #0, @-1
+      531 if intact(program, 531, 4) => {
+        let (a, b): (i64, i64) = (43, 0);
+        write(program, rb.checked_add(-1)?, a.checked_add(b)?)?;
+        *ptr = 535;
+      }
+      // 535: eq 1013, #43, 63
+      535 if intact(program, 535, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1013)?, 43);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 539;
+      }
+      // 539: jt 63, #551
+      539 if intact(program, 539, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(551)? } else { 542 };
+      }
+      // 542: out 531
+      542 if intact(program, 542, 2) => {
+        let value = read(program, 531)?;
+        *ptr = 544;
+        return Some(State::Output(value));
+      }
+      // 544: add 64, #1, 64
+      544 if intact(program, 544, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 548;
+      }
+      // 548: jf #0, #551
+      548 if intact(program, 548, 3) => {
+        *ptr = if 0 == 0 { address(551)? } else { 551 };
+      }
+      // 551: mul 64, #2, 64
+      551 if intact(program, 551, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 555;
+      }
+      // 555: arb #10
+      555 if intact(program, 555, 2) => {
+        *rb = rb.checked_add(10)?;
+        *ptr = 557;
+      }
+      // 557: jt #1, @0
+      557 if intact(program, 557, 3) => {
+        *ptr = if 1 != 0 { address(read(program, rb.checked_add(0)?)?)? } else { 560 };
+      }
+      // 560: out 557
+      560 if intact(program, 560, 2) => {
+        let value = read(program, 557)?;
+        *ptr = 562;
+        return Some(State::Output(value));
+      }
+      // 562: jf #0, #569
+      562 if intact(program, 562, 3) => {
+        *ptr = if 0 == 0 { address(569)? } else { 565 };
+      }
+      // 565: add 64, #1, 64
+      565 if intact(program, 565, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 569;
+      }
+      // 569: mul 64, #2, 64
+      569 if intact(program, 569, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 573;
+      }
+      // 573: arb #-12
+      573 if intact(program, 573, 2) => {
+        *rb = rb.checked_add(-12)?;
+        *ptr = 575;
+      }
+      // 575: mul #44, #1, @3
+      575 if intact(program, 575, 4) => {
+        let (a, b): (i64, i64) = (44, 1);
+        write(program, rb.checked_add(3)?, a.checked_mul(b)?)?;
+        *ptr = 579;
+      }
+      // 579: eq 1015, #44, 63
+      579 if intact(program, 579, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1015)?, 44);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 583;
+      }
+      // 583: jt 63, #595
+      583 if intact(program, 583, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(595)? } else { 586 };
+      }
+      // 586: out 575
+      586 if intact(program, 586, 2) => {
+        let value = read(program, 575)?;
+        *ptr = 588;
+        return Some(State::Output(value));
+      }
+      // 588: add 64, #1, 64
+      588 if intact(program, 588, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 592;
+      }
+      // 592: jt #1, #595
+      592 if intact(program, 592, 3) => {
+        *ptr = if 1 != 0 { address(595)? } else { 595 };
+      }
+      // 595: mul 64, #2, 64
+      595 if intact(program, 595, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 599;
+      }
+      // 599: arb #-4
+      599 if intact(program, 599, 2) => {
+        *rb = rb.checked_add(-4)?;
+        *ptr = 601;
+      }
+      // 601: add @-8, #0, 63
+      601 if intact(program, 601, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(-8)?)?, 0);
+        write(program, 63, a.checked_add(b)?)?;
+        *ptr = 605;
+      }
+      // 605: eq 63, #21, 63
+      605 if intact(program, 605, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 21);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 609;
+      }
+      // 609: jt 63, #621
+      609 if intact(program, 609, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(621)? } else { 612 };
+      }
+      // 612: out 601
+      612 if intact(program, 612, 2) => {
+        let value = read(program, 601)?;
+        *ptr = 614;
+        return Some(State::Output(value));
+      }
+      // 614: add 64, #1, 64
+      614 if intact(program, 614, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 618;
+      }
+      // 618: jf #0, #621
+      618 if intact(program, 618, 3) => {
+        *ptr = if 0 == 0 { address(621)? } else { 621 };
+      }
+      // 621: mul 64, #2, 64
+      621 if intact(program, 621, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 625;
+      }
+      // 625: arb #5
+      625 if intact(program, 625, 2) => {
+        *rb = rb.checked_add(5)?;
+        *ptr = 627;
+      }
+      // 627: eq #37, @-5, 63
+      627 if intact(program, 627, 4) => {
+        let (a, b): (i64, i64) = (37, read(program, rb.checked_add(-5)?)?);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 631;
+      }
+      // 631: jt 63, #639
+      631 if intact(program, 631, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(639)? } else { 634 };
+      }
+      // 634: out 627
+      634 if intact(program, 634, 2) => {
+        let value = read(program, 627)?;
+        *ptr = 636;
+        return Some(State::Output(value));
+      }
+      // 636: jt #1, #643
+      636 if intact(program, 636, 3) => {
+        *ptr = if 1 != 0 { address(643)? } else { 639 };
+      }
+      // 639: add 64, #1, 64
+      639 if intact(program, 639, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 643;
+      }
+      // 643: mul 64, #2, 64
+      643 if intact(program, 643, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 647;
+      }
+      // 647: arb #-14
+      647 if intact(program, 647, 2) => {
+        *rb = rb.checked_add(-14)?;
+        *ptr = 649;
+      }
+      // 649: mul @1, #1, 63
+      649 if intact(program, 649, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(1)?)?, 1);
+        write(program, 63, a.checked_mul(b)?)?;
+        *ptr = 653;
+      }
+      // 653: eq 63, #21, 63
+      653 if intact(program, 653, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 21);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 657;
+      }
+      // 657: jt 63, #669
+      657 if intact(program, 657, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(669)? } else { 660 };
+      }
+      // 660: out 649
+      660 if intact(program, 660, 2) => {
+        let value = read(program, 649)?;
+        *ptr = 662;
+        return Some(State::Output(value));
+      }
+      // 662: add 64, #1, 64
+      662 if intact(program, 662, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 666;
+      }
+      // 666: jt #1, #669
+      666 if intact(program, 666, 3) => {
+        *ptr = if 1 != 0 { address(669)? } else { 669 };
+      }
+      // 669: mul 64, #2, 64
+      669 if intact(program, 669, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 673;
+      }
+      // 673: arb #-2
+      673 if intact(program, 673, 2) => {
+        *rb = rb.checked_add(-2)?;
+        *ptr = 675;
+      }
+      // 675: lt @7, #27, 63
+      675 if intact(program, 675, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(7)?)?, 27);
+        write(program, 63, (a < b) as i64)?;
+        *ptr = 679;
+      }
+      // 679: jt 63, #691
+      679 if intact(program, 679, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(691)? } else { 682 };
+      }
+      // 682: out 675
+      682 if intact(program, 682, 2) => {
+        let value = read(program, 675)?;
+        *ptr = 684;
+        return Some(State::Output(value));
+      }
+      // 684: add 64, #1, 64
+      684 if intact(program, 684, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 688;
+      }
+      // 688: jf #0, #691
+      688 if intact(program, 688, 3) => {
+        *ptr = if 0 == 0 { address(691)? } else { 691 };
+      }
+      // 691: mul 64, #2, 64
+      691 if intact(program, 691, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 695;
+      }
+      // 695: arb #13
+      695 if intact(program, 695, 2) => {
+        *rb = rb.checked_add(13)?;
+        *ptr = 697;
+      }
+      // 697: lt #33, @-3, 63
+      697 if intact(program, 697, 4) => {
+        let (a, b): (i64, i64) = (33, read(program, rb.checked_add(-3)?)?);
+        write(program, 63, (a < b) as i64)?;
+        *ptr = 701;
+      }
+      // 701: jt 63, #711
+      701 if intact(program, 701, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(711)? } else { 704 };
+      }
+      // 704: add 64, #1, 64
+      704 if intact(program, 704, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 708;
+      }
+      // 708: jt #1, #713
+      708 if intact(program, 708, 3) => {
+        *ptr = if 1 != 0 { address(713)? } else { 711 };
+      }
+      // 711: out 697
+      711 if intact(program, 711, 2) => {
+        let value = read(program, 697)?;
+        *ptr = 713;
+        return Some(State::Output(value));
+      }
+      // 713: mul 64, #2, 64
+      713 if intact(program, 713, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 717;
+      }
+      // 717: arb #19
+      717 if intact(program, 717, 2) => {
+        *rb = rb.checked_add(19)?;
+        *ptr = 719;
+      }
+      // 719: jf @-9, #727
+      719 if intact(program, 719, 3) => {
+        *ptr = if read(program, rb.checked_add(-9)?)? == 0 { address(727)? } else { 722 };
+      }
+      // 722: out 719
+      722 if intact(program, 722, 2) => {
+        let value = read(program, 719)?;
+        *ptr = 724;
+        return Some(State::Output(value));
+      }
+      // 724: jt #1, #731
+      724 if intact(program, 724, 3) => {
+        *ptr = if 1 != 0 { address(731)? } else { 727 };
+      }
+      // 727: add 64, #1, 64
+      727 if intact(program, 727, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 731;
+      }
+      // 731: mul 64, #2, 64
+      731 if intact(program, 731, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 735;
+      }
+      // 735: arb #-24
+      735 if intact(program, 735, 2) => {
+        *rb = rb.checked_add(-24)?;
+        *ptr = 737;
+      }
+      // 737: mul @0, #1, 63
+      737 if intact(program, 737, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(0)?)?, 1);
+        write(program, 63, a.checked_mul(b)?)?;
+        *ptr = 741;
+      }
+      // 741: eq 63, #20, 63
+      741 if intact(program, 741, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 20);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 745;
+      }
+      // 745: jt 63, #755
+      745 if intact(program, 745, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(755)? } else { 748 };
+      }
+      // 748: add 64, #1, 64
+      748 if intact(program, 748, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 752;
+      }
+      // 752: jf #0, #757
+      752 if intact(program, 752, 3) => {
+        *ptr = if 0 == 0 { address(757)? } else { 755 };
+      }
+      // 755: out 737
+      755 if intact(program, 755, 2) => {
+        let value = read(program, 737)?;
+        *ptr = 757;
+        return Some(State::Output(value));
+      }
+      // 757: mul 64, #2, 64
+      757 if intact(program, 757, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 761;
+      }
+      // 761: arb #8
+      761 if intact(program, 761, 2) => {
+        *rb = rb.checked_add(8)?;
+        *ptr = 763;
+      }
+      // 763: mul #45, #1, @-3
+      763 if intact(program, 763, 4) => {
+        let (a, b): (i64, i64) = (45, 1);
+        write(program, rb.checked_add(-3)?, a.checked_mul(b)?)?;
+        *ptr = 767;
+      }
+      // 767: eq 1010, #46, 63
+      767 if intact(program, 767, 4) => {
+        let (a, b): (i64, i64) = (read(program, 1010)?, 46);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 771;
+      }
+      // 771: jt 63, #781
+      771 if intact(program, 771, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(781)? } else { 774 };
+      }
+      // 774: add 64, #1, 64
+      774 if intact(program, 774, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 778;
+      }
+      // 778: jf #0, #783
+      778 if intact(program, 778, 3) => {
+        *ptr = if 0 == 0 { address(783)? } else { 781 };
+      }
+      // 781: out 763
+      781 if intact(program, 781, 2) => {
+        let value = read(program, 763)?;
+        *ptr = 783;
+        return Some(State::Output(value));
+      }
+      // 783: mul 64, #2, 64
+      783 if intact(program, 783, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 787;
+      }
+      // 787: arb #-15
+      787 if intact(program, 787, 2) => {
+        *rb = rb.checked_add(-15)?;
+        *ptr = 789;
+      }
+      // 789: eq #40, @10, 63
+      789 if intact(program, 789, 4) => {
+        let (a, b): (i64, i64) = (40, read(program, rb.checked_add(10)?)?);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 793;
+      }
+      // 793: jt 63, #799
+      793 if intact(program, 793, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(799)? } else { 796 };
+      }
+      // 796: jt #1, #805
+      796 if intact(program, 796, 3) => {
+        *ptr = if 1 != 0 { address(805)? } else { 799 };
+      }
+      // 799: out 789
+      799 if intact(program, 799, 2) => {
+        let value = read(program, 789)?;
+        *ptr = 801;
+        return Some(State::Output(value));
+      }
+      // 801: add 64, #1, 64
+      801 if intact(program, 801, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 805;
+      }
+      // 805: mul 64, #2, 64
+      805 if intact(program, 805, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 809;
+      }
+      // 809: arb #20
+      809 if intact(program, 809, 2) => {
+        *rb = rb.checked_add(20)?;
+        *ptr = 811;
+      }
+      // 811: lt #46, #45, @-1
+      811 if intact(program, 811, 4) => {
+        let (a, b): (i64, i64) = (46, 45);
+        write(program, rb.checked_add(-1)?, (a < b) as i64)?;
+        *ptr = 815;
+      }
+      // 815: jt 1017, #821
+      815 if intact(program, 815, 3) => {
+        *ptr = if read(program, 1017)? != 0 { address(821)? } else { 818 };
+      }
+      // 818: jf #0, #827
+      818 if intact(program, 818, 3) => {
+        *ptr = if 0 == 0 { address(827)? } else { 821 };
+      }
+      // 821: out 811
+      821 if intact(program, 821, 2) => {
+        let value = read(program, 811)?;
+        *ptr = 823;
+        return Some(State::Output(value));
+      }
+      // 823: add 64, #1, 64
+      823 if intact(program, 823, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 827;
+      }
+      // 827: mul 64, #2, 64
+      827 if intact(program, 827, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 831;
+      }
+      // 831: arb #-23
+      831 if intact(program, 831, 2) => {
+        *rb = rb.checked_add(-23)?;
+        *ptr = 833;
+      }
+      // 833: add @6, #0, 63
+      833 if intact(program, 833, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(6)?)?, 0);
+        write(program, 63, a.checked_add(b)?)?;
+        *ptr = 837;
+      }
+      // 837: eq 63, #29, 63
+      837 if intact(program, 837, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 29);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 841;
+      }
+      // 841: jt 63, #847
+      841 if intact(program, 841, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(847)? } else { 844 };
+      }
+      // 844: jf #0, #853
+      844 if intact(program, 844, 3) => {
+        *ptr = if 0 == 0 { address(853)? } else { 847 };
+      }
+      // 847: out 833
+      847 if intact(program, 847, 2) => {
+        let value = read(program, 833)?;
+        *ptr = 849;
+        return Some(State::Output(value));
+      }
+      // 849: add 64, #1, 64
+      849 if intact(program, 849, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 853;
+      }
+      // 853: mul 64, #2, 64
+      853 if intact(program, 853, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 857;
+      }
+      // 857: arb #17
+      857 if intact(program, 857, 2) => {
+        *rb = rb.checked_add(17)?;
+        *ptr = 859;
+      }
+      // 859: lt #47, #48, @2
+      859 if intact(program, 859, 4) => {
+        let (a, b): (i64, i64) = (47, 48);
+        write(program, rb.checked_add(2)?, (a < b) as i64)?;
+        *ptr = 863;
+      }
+      // 863: jt 1014, #875
+      863 if intact(program, 863, 3) => {
+        *ptr = if read(program, 1014)? != 0 { address(875)? } else { 866 };
+      }
+      // 866: out 859
+      866 if intact(program, 866, 2) => {
+        let value = read(program, 859)?;
+        *ptr = 868;
+        return Some(State::Output(value));
+      }
+      // 868: add 64, #1, 64
+      868 if intact(program, 868, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 872;
+      }
+      // 872: jf #0, #875
+      872 if intact(program, 872, 3) => {
+        *ptr = if 0 == 0 { address(875)? } else { 875 };
+      }
+      // 875: mul 64, #2, 64
+      875 if intact(program, 875, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 2);
+        write(program, 64, a.checked_mul(b)?)?;
+        *ptr = 879;
+      }
+      // 879: arb #-10
+      879 if intact(program, 879, 2) => {
+        *rb = rb.checked_add(-10)?;
+        *ptr = 881;
+      }
+      // 881: add #0, @-2, 63
+      881 if intact(program, 881, 4) => {
+        let (a, b): (i64, i64) = (0, read(program, rb.checked_add(-2)?)?);
+        write(program, 63, a.checked_add(b)?)?;
+        *ptr = 885;
+      }
+      // 885: eq 63, #20, 63
+      885 if intact(program, 885, 4) => {
+        let (a, b): (i64, i64) = (read(program, 63)?, 20);
+        write(program, 63, (a == b) as i64)?;
+        *ptr = 889;
+      }
+      // 889: jt 63, #895
+      889 if intact(program, 889, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(895)? } else { 892 };
+      }
+      // 892: jt #1, #901
+      892 if intact(program, 892, 3) => {
+        *ptr = if 1 != 0 { address(901)? } else { 895 };
+      }
+      // 895: out 881
+      895 if intact(program, 895, 2) => {
+        let value = read(program, 881)?;
+        *ptr = 897;
+        return Some(State::Output(value));
+      }
+      // 897: add 64, #1, 64
+      897 if intact(program, 897, 4) => {
+        let (a, b): (i64, i64) = (read(program, 64)?, 1);
+        write(program, 64, a.checked_add(b)?)?;
+        *ptr = 901;
+      }
+      // 901: out 64
+      901 if intact(program, 901, 2) => {
+        let value = read(program, 64)?;
+        *ptr = 903;
+        return Some(State::Output(value));
+      }
+      // 903: hlt
+      903 if intact(program, 903, 1) => {
+        return Some(State::Halted);
+      }
+      // 904: mul #27, #1, @1
+      904 if intact(program, 904, 4) => {
+        let (a, b): (i64, i64) = (27, 1);
+        write(program, rb.checked_add(1)?, a.checked_mul(b)?)?;
+        *ptr = 908;
+      }
+      // 908: add #0, #915, @0
+      908 if intact(program, 908, 4) => {
+        let (a, b): (i64, i64) = (0, 915);
+        write(program, rb.checked_add(0)?, a.checked_add(b)?)?;
+        *ptr = 912;
+      }
+      // 912: jt #1, #922
+      912 if intact(program, 912, 3) => {
+        *ptr = if 1 != 0 { address(922)? } else { 915 };
+      }
+      // 915: add @1, #37574, @1
+      915 if intact(program, 915, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(1)?)?, 37574);
+        write(program, rb.checked_add(1)?, a.checked_add(b)?)?;
+        *ptr = 919;
+      }
+      // 919: out @1
+      919 if intact(program, 919, 2) => {
+        let value = read(program, rb.checked_add(1)?)?;
+        *ptr = 921;
+        return Some(State::Output(value));
+      }
+      // 921: hlt
+      921 if intact(program, 921, 1) => {
+        return Some(State::Halted);
+      }
+      // 922: arb #3
+      922 if intact(program, 922, 2) => {
+        *rb = rb.checked_add(3)?;
+        *ptr = 924;
+      }
+      // 924: lt @-2, #3, 63
+      924 if intact(program, 924, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(-2)?)?, 3);
+        write(program, 63, (a < b) as i64)?;
+        *ptr = 928;
+      }
+      // 928: jt 63, #964
+      928 if intact(program, 928, 3) => {
+        *ptr = if read(program, 63)? != 0 { address(964)? } else { 931 };
+      }
+      // 931: add @-2, #-1, @1
+      931 if intact(program, 931, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(-2)?)?, -1);
+        write(program, rb.checked_add(1)?, a.checked_add(b)?)?;
+        *ptr = 935;
+      }
+      // 935: mul #942, #1, @0
+      935 if intact(program, 935, 4) => {
+        let (a, b): (i64, i64) = (942, 1);
+        write(program, rb.checked_add(0)?, a.checked_mul(b)?)?;
+        *ptr = 939;
+      }
+      // 939: jt #1, #922
+      939 if intact(program, 939, 3) => {
+        *ptr = if 1 != 0 { address(922)? } else { 942 };
+      }
+      // 942: mul #1, @1, @-1
+      942 if intact(program, 942, 4) => {
+        let (a, b): (i64, i64) = (1, read(program, rb.checked_add(1)?)?);
+        write(program, rb.checked_add(-1)?, a.checked_mul(b)?)?;
+        *ptr = 946;
+      }
+      // 946: add @-2, #-3, @1
+      946 if intact(program, 946, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(-2)?)?, -3);
+        write(program, rb.checked_add(1)?, a.checked_add(b)?)?;
+        *ptr = 950;
+      }
+      // 950: add #957, #0, @0
+      950 if intact(program, 950, 4) => {
+        let (a, b): (i64, i64) = (957, 0);
+        write(program, rb.checked_add(0)?, a.checked_add(b)?)?;
+        *ptr = 954;
+      }
+      // 954: jt #1, #922
+      954 if intact(program, 954, 3) => {
+        *ptr = if 1 != 0 { address(922)? } else { 957 };
+      }
+      // 957: add @1, @-1, @-2
+      957 if intact(program, 957, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(1)?)?, read(program, rb.checked_add(-1)?)?);
+        write(program, rb.checked_add(-2)?, a.checked_add(b)?)?;
+        *ptr = 961;
+      }
+      // 961: jt #1, #968
+      961 if intact(program, 961, 3) => {
+        *ptr = if 1 != 0 { address(968)? } else { 964 };
+      }
+      // 964: add @-2, #0, @-2
+      964 if intact(program, 964, 4) => {
+        let (a, b): (i64, i64) = (read(program, rb.checked_add(-2)?)?, 0);
+        write(program, rb.checked_add(-2)?, a.checked_add(b)?)?;
+        *ptr = 968;
+      }
+      // 968: arb #-3
+      968 if intact(program, 968, 2) => {
+        *rb = rb.checked_add(-3)?;
+        *ptr = 970;
+      }
+      // 970: jt #1, @0
+      970 if intact(program, 970, 3) => {
+        *ptr = if 1 != 0 { address(read(program, rb.checked_add(0)?)?)? } else { 973 };
+      }
+      _ => return None,
+    }
+  }
+}
@@ -0,0 +1,31 @@
+use advent19::{parse_intcode, replay, Program, Recording};
+
+// Sessions recorded with `cargo run --bin day11 inputs/day11.txt tests/replays/day11.replay`,
+// and the same for day 13.
+fn check(source: &str, session: &str) {
+  let code = parse_intcode(source).unwrap();
+  let recording = Recording::load(session.as_bytes()).unwrap();
+  for &cached in &[true, false] {
+    let mut program = Program::new(code.clone());
+    program.set_decode_cache(cached);
+    if let Some(divergence) = replay(program, &recording).unwrap() {
+      panic!("replay diverges at {}", divergence);
+    }
+  }
+}
+
+#[test]
+fn day11_painter() {
+  check(
+    include_str!("../inputs/day11.txt"),
+    include_str!("replays/day11.replay"),
+  );
+}
+
+#[test]
+fn day13_arcade() {
+  check(
+    include_str!("../inputs/day13.txt"),
+    include_str!("replays/day13.replay"),
+  );
+}
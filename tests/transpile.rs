@@ -0,0 +1,74 @@
+use advent19::{parse_intcode, transpile, IntcodeError, Program, State};
+
+#[rustfmt::skip]
+#[path = "generated/day9.rs"]
+mod day9;
+
+fn drive(
+  program: &mut Program,
+  run: impl Fn(&mut Program) -> Result<State, IntcodeError>,
+) -> Result<(Vec<i64>, State), IntcodeError> {
+  let mut outputs = Vec::new();
+  loop {
+    match run(program)? {
+      State::Output(value) => outputs.push(value),
+      state => return Ok((outputs, state)),
+    }
+  }
+}
+
+fn compare(program: Program) -> Result<(Vec<i64>, State), IntcodeError> {
+  let mut interpreted = Program::new(program.memory().to_vec());
+  interpreted.extend_input(program.input().iter().copied());
+  let mut native = program;
+  let expected = drive(&mut interpreted, Program::run);
+  assert_eq!(drive(&mut native, day9::run), expected);
+  assert_eq!(native.ptr(), interpreted.ptr());
+  assert_eq!(native.relative_base(), interpreted.relative_base());
+  assert_eq!(native.memory().to_vec(), interpreted.memory().to_vec());
+  expected
+}
+
+#[test]
+fn generated_code_is_up_to_date() {
+  let code = parse_intcode(include_str!("../inputs/day9.txt")).unwrap();
+  assert!(
+    transpile(&code).to_string() == include_str!("generated/day9.rs"),
+    "regenerate with `cargo run --bin intcode-transpile inputs/day9.txt > tests/generated/day9.rs`"
+  );
+}
+
+#[test]
+fn matches_interpreter() {
+  for input in 1..=2 {
+    let mut program = Program::new(day9::CODE);
+    program.push_input(input);
+    let (outputs, state) = compare(program).unwrap();
+    assert_eq!((outputs.len(), state), (1, State::Halted));
+  }
+
+  let (outputs, state) = compare(Program::new(day9::CODE)).unwrap();
+  assert_eq!((outputs, state), (vec![], State::NeedsInput));
+}
+
+#[test]
+fn falls_back_on_self_modified_code() {
+  // mul #521, #1, 1028 becomes add #521, #0, 1028
+  let mut program = Program::new(day9::CODE);
+  program.memory_mut().set(65, 1101).unwrap();
+  program.memory_mut().set(67, 0).unwrap();
+  program.push_input(1);
+  assert_eq!(compare(program).unwrap().0, vec![2714716640]);
+
+  // a jump table entry now points into the middle of an instruction
+  let mut program = Program::new(day9::CODE);
+  program.memory_mut().set(66, 522).unwrap();
+  program.push_input(1);
+  assert_eq!(
+    compare(program),
+    Err(IntcodeError::UnknownOpcode {
+      ptr: 522,
+      instruction: 64
+    })
+  );
+}